@@ -0,0 +1,69 @@
+use serde_json::{Value, json};
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+    sync::Mutex
+};
+
+#[derive(Debug)]
+/// # Audit Log
+///
+/// An append-only, human-readable record of every mutation, buffered in memory until the next save
+///
+/// Unlike the journal it is never replayed. The buffer sits behind a mutex so `.save()` can drain it through a shared reference
+pub(crate) struct AuditLog {
+    path: PathBuf,
+    pending: Mutex<Vec<String>>
+}
+
+impl AuditLog {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        AuditLog { path, pending: Mutex::new(Vec::new()) }
+    }
+
+    /// # Record
+    ///
+    /// Buffer one `{"ts", "op", "key", "old", "new"}` line, with both values replaced by `"<redacted>"` for secret keys
+    /// and a missing value written as null
+    pub(crate) fn record(&self, ts: u64, op: &str, key: &str, old: Option<&Value>, new: Option<&Value>, secret: bool) {
+        let show = |value: Option<&Value>| match value {
+            Some(_) if secret => Value::from("<redacted>"),
+            Some(value) => value.clone(),
+            None => Value::Null
+        };
+        let line = json!({ "ts": ts, "op": op, "key": key, "old": show(old), "new": show(new) }).to_string();
+        self.lock().push(line);
+    }
+
+    /// # Flush
+    ///
+    /// Append every buffered line to the audit file in a single write, keeping them buffered if that fails
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        let mut pending = self.lock();
+        if pending.is_empty() { return Ok(()); }
+        let mut lines = pending.join("\n");
+        lines.push('\n');
+        OpenOptions::new().create(true).append(true).open(&self.path)?.write_all(lines.as_bytes())?;
+        pending.clear();
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// # Hand Off
+    ///
+    /// A copy of the log that takes over the buffered lines, leaving this one with none, for `.reload()` to pass them on
+    pub(crate) fn hand_off(&self) -> Self {
+        AuditLog { path: self.path.clone(), pending: Mutex::new(std::mem::take(&mut *self.lock())) }
+    }
+}
+
+/// A clone starts with nothing buffered, so two copies of a config never append the same lines on their next saves
+impl Clone for AuditLog {
+    fn clone(&self) -> Self {
+        AuditLog::new(self.path.clone())
+    }
+}
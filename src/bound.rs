@@ -0,0 +1,52 @@
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::ops::{Deref, DerefMut};
+use crate::{FigCon, FigConError, FigConRoot, typed};
+
+#[derive(Debug)]
+/// # Bound FigCon
+///
+/// A typed view of a FigCon, from `FigCon::bind()`: the config deserialized into a struct `T`, editable through its fields
+///
+/// Edits stay in the struct until `.commit()` writes them back. Dropping the handle without committing discards them
+pub struct BoundFigCon<'a, T> {
+    conf: &'a mut FigCon,
+    value: T
+}
+
+impl<'a, T: Serialize + DeserializeOwned> BoundFigCon<'a, T> {
+    pub(crate) fn new(conf: &'a mut FigCon) -> Result<Self, FigConError> {
+        let value = typed::deserialize_traced(&conf.live_config).map_err(|(path, error)| FigConError::Typed { path, error })?;
+        Ok(BoundFigCon { conf, value })
+    }
+
+    /// # Commit
+    ///
+    /// Serialize the struct back into the config, one top-level key per field, as a single `set_many`
+    ///
+    /// Config keys that `T` has no field for are preserved, and so are keys for fields skipped while serializing
+    /// (like `#[serde(skip_serializing_if = "Option::is_none")]`).
+    /// Fails with `FigConError::WrongRoot` if `T` doesn't serialize to an object, and otherwise like `FigCon::try_set_many()`
+    pub fn commit(self) -> Result<(), FigConError> {
+        match serde_json::to_value(&self.value).map_err(FigConError::Serialize)? {
+            Value::Object(fields) => {
+                self.conf.try_set_many(fields.into_iter().collect())
+            },
+            other => Err(FigConError::WrongRoot { expected: FigConRoot::Object, found: FigConRoot::of(&other) })
+        }
+    }
+}
+
+impl<T> Deref for BoundFigCon<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for BoundFigCon<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
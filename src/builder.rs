@@ -0,0 +1,155 @@
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::{
+    fs,
+    path::PathBuf
+};
+use crate::{ArrayMergeStrategy, FigCon, FigConError, FigConReport, Format, Severity, env, fields, merge};
+
+#[derive(Clone, Debug, Default)]
+/// # FigCon Builder
+///
+/// Declarative setup for the "shipped defaults + user customization" pattern, started with `FigCon::builder()`
+pub struct FigConBuilder {
+    defaults: Option<Vec<u8>>,
+    path: PathBuf,
+    format: Format,
+    env_prefix: Option<String>,
+    known_fields: Option<&'static [&'static str]>,
+    validate: Option<Validator>
+}
+
+/// # Validator
+///
+/// A check run over the final config by `FigCon::load_with_report()`, describing what's wrong
+type Validator = fn(&Value) -> Result<(), String>;
+
+/// # Validate As
+///
+/// Check that a value deserializes into `T`, describing why not
+fn validate_as<T: DeserializeOwned>(value: &Value) -> Result<(), String> {
+    T::deserialize(value).map(|_| ()).map_err(|err| err.to_string())
+}
+
+impl FigConBuilder {
+    /// # Defaults Bytes
+    ///
+    /// Start from a config embedded in the program (like `include_bytes!("defaults.json")`), in the builder's format
+    pub fn defaults_bytes(mut self, bytes: &[u8]) -> Self {
+        self.defaults = Some(bytes.to_vec());
+        self
+    }
+
+    /// # Override File
+    ///
+    /// Deep-merge the user's file over the defaults if it exists, and bind `.save()` to it
+    pub fn override_file(mut self, path: PathBuf) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// # Format
+    ///
+    /// The format of both the default bytes and the override file (JSON by default)
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// # Env Overlay
+    ///
+    /// Finish by overriding existing settings from environment variables with this prefix, like `FigCon::overlay_env_existing()`
+    ///
+    /// `FigCon::load_with_report()` warns about prefixed variables that matched no setting
+    pub fn env_overlay(mut self, prefix: &str) -> Self {
+        self.env_prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// # Validate As
+    ///
+    /// Have `FigCon::load_with_report()` check the final config against the struct `T`:
+    /// a config that doesn't deserialize into `T` is an error, and top-level keys `T` has no field for are warnings
+    ///
+    /// `.build()` skips these checks
+    pub fn validate_as<T: DeserializeOwned>(mut self) -> Self {
+        self.known_fields = fields::struct_fields::<T>();
+        self.validate = Some(validate_as::<T>);
+        self
+    }
+
+    /// # Build
+    ///
+    /// Parse the defaults, merge the override file over them and return the config
+    ///
+    /// A missing override file is fine and leaves just the defaults. Malformed defaults or a malformed override file are errors
+    pub fn build(self) -> Result<FigCon, FigConError> {
+        let mut value = self.read_defaults()?;
+        if let Some(overrides) = self.read_override()? {
+            merge::merge_into(&mut value, overrides, ArrayMergeStrategy::Replace, false, 0);
+        }
+        Ok(self.finish(value, &mut FigConReport::default()))
+    }
+
+    /// # Build With Report
+    ///
+    /// Like `.build()`, but never fails: problems are collected in the report and the load carries on without the broken part
+    pub(crate) fn build_with_report(self) -> (FigCon, FigConReport) {
+        let mut report = FigConReport::default();
+        let mut value = self.read_defaults().unwrap_or_else(|err| {
+            report.push(Severity::Error, "defaults", format!("embedded defaults are unusable, starting empty: {}", err));
+            Value::Object(Map::new())
+        });
+        match self.read_override() {
+            Ok(Some(overrides)) => merge::merge_into(&mut value, overrides, ArrayMergeStrategy::Replace, false, 0),
+            Ok(None) => report.push(Severity::Info, "override", format!("{} doesn't exist, using the defaults", self.path.display())),
+            Err(err) => report.push(Severity::Error, "override", format!("{} was ignored: {}", self.path.display(), err))
+        }
+        let conf = self.finish(value, &mut report);
+        for key in conf.reserved_key_conflicts() {
+            report.push(Severity::Warning, "validate", format!("\"{}\" is a reserved key", key));
+        }
+        if let Some(fields) = self.known_fields {
+            for key in conf.list_keys().unwrap_or_default().into_iter().filter(|key| !fields.contains(&key.as_str())) {
+                report.push(Severity::Warning, "validate", format!("unknown key \"{}\"", key));
+            }
+        }
+        if let Some(validate) = self.validate && let Err(err) = validate(&conf.live_config) {
+            report.push(Severity::Error, "validate", err);
+        }
+        (conf, report)
+    }
+
+    /// # Read Defaults
+    ///
+    /// Parse the embedded defaults, or start from an empty object without any
+    fn read_defaults(&self) -> Result<Value, FigConError> {
+        match &self.defaults {
+            Some(bytes) => self.format.decode(bytes),
+            None => Ok(Value::Object(Map::new()))
+        }
+    }
+
+    /// # Read Override
+    ///
+    /// Parse the override file, or None if it doesn't exist
+    fn read_override(&self) -> Result<Option<Value>, FigConError> {
+        if !self.path.is_file() { return Ok(None); }
+        Ok(Some(self.format.decode(&fs::read(&self.path)?)?))
+    }
+
+    /// # Finish
+    ///
+    /// Wrap the layered value in a config bound to the override file and apply the env overlay, warning about skipped variables
+    fn finish(&self, value: Value, report: &mut FigConReport) -> FigCon {
+        let mut conf = FigCon::from_parts(value, self.path.clone());
+        conf.format = self.format;
+        if let Some(prefix) = &self.env_prefix {
+            let applied = conf.overlay_env_existing(prefix);
+            for (name, _, _) in env::overrides(prefix, conf.path_separator).into_iter().filter(|(_, path, _)| !applied.contains(path)) {
+                report.push(Severity::Warning, "env", format!("{} matches no setting and was skipped", name));
+            }
+        }
+        conf
+    }
+}
@@ -0,0 +1,16 @@
+use serde_json::Value;
+
+/// # Record Change
+///
+/// Emit one structured line for a mutation, like `config.set key=port old=8080 new=9090`
+///
+/// Logged at `info` level under the `figcon` target. Secret keys have both values replaced with `<redacted>`,
+/// and a missing old or new value is written as `-`
+pub(crate) fn record(op: &str, key: &str, old: Option<&Value>, new: Option<&Value>, secret: bool) {
+    let show = |value: Option<&Value>| match value {
+        None => "-".to_owned(),
+        Some(_) if secret => "<redacted>".to_owned(),
+        Some(value) => value.to_string()
+    };
+    log::info!(target: "figcon", "config.{} key={} old={} new={}", op, key, show(old), show(new));
+}
@@ -0,0 +1,78 @@
+use alloc::string::{String, ToString};
+use serde_json::Value;
+
+/// # Coerce f64
+///
+/// Any JSON number, or a string holding a finite number
+pub(crate) fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.trim().parse::<f64>().ok().filter(|number| number.is_finite()),
+        _ => None
+    }
+}
+
+/// # Integral
+///
+/// A float with no fractional part, which can stand in for an integer
+fn integral(number: f64) -> Option<f64> {
+    (number.is_finite() && number % 1.0 == 0.0).then_some(number)
+}
+
+/// # Coerce i64
+///
+/// An integer, a float with no fractional part, or a string holding either, as long as it fits
+pub(crate) fn to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64().or_else(|| {
+            integral(number.as_f64()?).filter(|number| *number >= i64::MIN as f64 && *number < i64::MAX as f64).map(|number| number as i64)
+        }),
+        Value::String(string) => string.trim().parse::<i64>().ok().or_else(|| to_i64(&Value::from(to_f64(value)?))),
+        _ => None
+    }
+}
+
+/// # Coerce u64
+///
+/// A non-negative integer, a float with no fractional part, or a string holding either, as long as it fits
+pub(crate) fn to_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64().or_else(|| {
+            integral(number.as_f64()?).filter(|number| *number >= 0.0 && *number < u64::MAX as f64).map(|number| number as u64)
+        }),
+        Value::String(string) => string.trim().parse::<u64>().ok().or_else(|| to_u64(&Value::from(to_f64(value)?))),
+        _ => None
+    }
+}
+
+/// # Coerce bool
+///
+/// A boolean, the numbers 0 and 1, or one of the strings true/false, yes/no, on/off, 1/0 (case-insensitive)
+pub(crate) fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(boolean) => Some(*boolean),
+        Value::Number(number) => match number.as_u64() {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            _ => None
+        },
+        Value::String(string) => match string.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" => Some(false),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+/// # Coerce String
+///
+/// A string as-is, or a number or boolean written out as text
+pub(crate) fn to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None
+    }
+}
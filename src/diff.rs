@@ -0,0 +1,57 @@
+use serde_json::Value;
+use crate::MAX_DEPTH;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// # FigCon Change
+/// 
+/// One difference between two configs, addressed by dot-path (or a path with the config's `with_path_separator`)
+/// 
+/// Objects are compared key by key; arrays and scalars are compared as a whole
+pub enum FigConChange {
+    Added { path: String, value: Value },
+    Removed { path: String, value: Value },
+    Changed { path: String, old: Value, new: Value }
+}
+
+impl FigConChange {
+    /// # Path
+    /// 
+    /// The dot-path the change happened at
+    pub fn path(&self) -> &str {
+        match self {
+            FigConChange::Added { path, .. } | FigConChange::Removed { path, .. } | FigConChange::Changed { path, .. } => path
+        }
+    }
+}
+
+/// # Join
+/// 
+/// Append a key to a path
+fn join(prefix: &str, key: &str, sep: char) -> String {
+    if prefix.is_empty() { key.to_owned() } else { format!("{}{}{}", prefix, sep, key) }
+}
+
+/// # Diff Into
+/// 
+/// Push every change needed to turn `old` into `new`, found `depth` containers deep
+/// 
+/// Objects from `MAX_DEPTH` down are compared whole, as a single change
+pub(crate) fn diff_into(prefix: &str, old: &Value, new: &Value, sep: char, out: &mut Vec<FigConChange>, depth: usize) {
+    match (old, new) {
+        (Value::Object(old_object), Value::Object(new_object)) if depth < MAX_DEPTH => {
+            for (key, old_value) in old_object {
+                match new_object.get(key) {
+                    Some(new_value) => diff_into(&join(prefix, key, sep), old_value, new_value, sep, out, depth + 1),
+                    None => out.push(FigConChange::Removed { path: join(prefix, key, sep), value: old_value.clone() })
+                }
+            }
+            for (key, new_value) in new_object {
+                if !old_object.contains_key(key) {
+                    out.push(FigConChange::Added { path: join(prefix, key, sep), value: new_value.clone() });
+                }
+            }
+        },
+        _ if old != new => out.push(FigConChange::Changed { path: prefix.to_owned(), old: old.clone(), new: new.clone() }),
+        _ => {}
+    }
+}
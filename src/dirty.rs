@@ -0,0 +1,32 @@
+use std::sync::atomic::{
+    AtomicBool,
+    Ordering
+};
+
+#[derive(Debug, Default)]
+/// # Dirty Flag
+///
+/// Whether the live config has changed since it was loaded or last saved
+///
+/// Atomic so `.save()` can clear it through a shared reference while FigCon stays `Sync`
+pub(crate) struct DirtyFlag(AtomicBool);
+
+impl DirtyFlag {
+    pub(crate) fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn mark(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn clear(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Clone for DirtyFlag {
+    fn clone(&self) -> Self {
+        DirtyFlag(AtomicBool::new(self.get()))
+    }
+}
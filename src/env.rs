@@ -0,0 +1,39 @@
+use serde_json::Value;
+use std::env;
+
+/// # Key Path
+///
+/// Turn an environment variable name into a config path with `sep` between keys, if it starts with `prefix`
+///
+/// The rest of the name is lowercased and `__` marks a nesting level, so with the prefix `APP_`,
+/// `APP_LOG_LEVEL` becomes `log_level` and `APP_SERVER__PORT` becomes `server.port`
+pub(crate) fn key_path(name: &str, prefix: &str, sep: char) -> Option<String> {
+    let rest = name.strip_prefix(prefix)?;
+    if rest.is_empty() || rest.split("__").any(str::is_empty) { return None; }
+    Some(rest.to_lowercase().replace("__", sep.encode_utf8(&mut [0; 4])))
+}
+
+/// # Parse Value
+///
+/// Read an environment variable's text as JSON when it is valid JSON (numbers, bools, arrays, ...),
+/// and as a plain string otherwise
+pub(crate) fn parse_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_owned()))
+}
+
+/// # Overrides
+///
+/// Every environment variable with the prefix as its name, path and value, sorted by path
+///
+/// Variables whose name or value isn't valid unicode are skipped
+pub(crate) fn overrides(prefix: &str, sep: char) -> Vec<(String, String, Value)> {
+    let mut found: Vec<(String, String, Value)> = env::vars_os()
+        .filter_map(|(name, value)| {
+            let name = name.into_string().ok()?;
+            let path = key_path(&name, prefix, sep)?;
+            Some((name, path, parse_value(value.to_str()?)))
+        })
+        .collect();
+    found.sort_by(|(_, left, _), (_, right, _)| left.cmp(right));
+    found
+}
@@ -0,0 +1,200 @@
+use crate::{FigConRoot, Format};
+use std::{
+    error::Error,
+    fmt::Display,
+    io,
+    path::PathBuf
+};
+
+#[derive(Debug)]
+/// # FigCon Error
+/// 
+/// Everything that can go wrong while loading or saving a config
+pub enum FigConError {
+    /// Reading or writing the config file failed
+    Io(io::Error),
+    /// The config file isn't valid JSON. The snippet quotes the offending line with a caret under the column
+    Parse {
+        error: serde_json::Error,
+        snippet: String
+    },
+    /// The config file's text encoding isn't supported or is malformed
+    Encoding(String),
+    /// The live config couldn't be serialized
+    Serialize(serde_json::Error),
+    /// The config nests deeper than a depth limit: the global `MAX_DEPTH` (128), checked on every load and setter,
+    /// or the stricter limit given to `load_with_max_depth`. `limit` is whichever one was exceeded
+    TooDeep {
+        limit: usize
+    },
+    /// A strict-paths set would have replaced the object or array at this key (or dot-path) with a scalar
+    WouldReplaceContainer {
+        key: String
+    },
+    /// A dot-path setter ran into a scalar (or an array it can't index) at this dot-path (empty for the root),
+    /// which it won't replace with an object
+    PathBlocked {
+        path: String
+    },
+    /// An array method was used on a config whose root isn't an array
+    WrongRoot {
+        expected: FigConRoot,
+        found: FigConRoot
+    },
+    /// An index past the end of an array-rooted config
+    IndexOutOfBounds {
+        index: usize,
+        len: usize
+    },
+    /// A float setter was given NaN or infinity, which JSON can't represent
+    UnrepresentableNumber {
+        key: String,
+        value: f64
+    },
+    /// One file of a config directory (`FigCon::load_dir`) or of an `extends` chain failed to load
+    Fragment {
+        path: PathBuf,
+        error: Box<FigConError>
+    },
+    /// A JSON Patch operation (counting from 0) couldn't be applied, or its `test` failed, so the patch was rejected
+    PatchFailed {
+        index: usize,
+        reason: String
+    },
+    /// `FigCon::load_auto` couldn't parse the file as any of these formats, tried in this order
+    NoFormatMatched {
+        tried: Vec<Format>
+    },
+    /// The file's extension names a format that isn't built in, like TOML or YAML (Load it through a custom `Codec`)
+    UnsupportedFormat {
+        extension: String
+    },
+    /// The config doesn't fit the type given to `FigCon::load_typed`. The path is the dot-path of the rejected value (empty for the root)
+    Typed {
+        path: String,
+        error: serde_json::Error
+    },
+    /// An `extends` chain came back around to this file, which it already extends
+    ExtendsCycle {
+        path: PathBuf
+    },
+    /// `FigCon::load_checked` was given a path that can't name a config file, or `FigConGroup::save_all` found two configs
+    /// saving to this one, for the reason given
+    InvalidPath {
+        path: PathBuf,
+        reason: String
+    },
+    /// The config path points at an existing directory, so there's no file to save to
+    PathIsDirectory {
+        path: PathBuf
+    },
+    /// The config path's parent (this path) exists but isn't a directory, so no file can be created inside it
+    ParentNotDirectory {
+        path: PathBuf
+    },
+    /// A custom codec (`FigCon::with_codec`) couldn't encode or decode the config
+    Codec(String),
+    /// The storage backend is out of space, like a browser's localStorage quota
+    QuotaExceeded,
+    /// Encoding or decoding a binary format failed
+    #[cfg(feature = "binary")]
+    Binary(String),
+    /// The config didn't survive being serialized and parsed again; the path (empty for the root) is the first place it differed
+    #[cfg(feature = "testing")]
+    RoundTrip {
+        path: String
+    }
+}
+
+impl Display for FigConError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FigConError::Io(err) => write!(f, "config IO failed: {}", err),
+            FigConError::Parse { error, snippet } if snippet.is_empty() => write!(f, "config JSON deserialization failed: {}", error),
+            FigConError::Parse { error, snippet } => write!(f, "config JSON deserialization failed: {}\n{}", error, snippet),
+            FigConError::Encoding(err) => write!(f, "config encoding unsupported: {}", err),
+            FigConError::Serialize(err) => write!(f, "config JSON serialization failed: {}", err),
+            FigConError::TooDeep { limit } => write!(f, "config nests deeper than the depth limit ({})", limit),
+            FigConError::WouldReplaceContainer { key } => write!(f, "refusing to replace the section at \"{}\" with a scalar", key),
+            FigConError::PathBlocked { path } if path.is_empty() => write!(f, "config path can't be set through the root value"),
+            FigConError::PathBlocked { path } => write!(f, "config path can't be set through the value at \"{}\"", path),
+            FigConError::WrongRoot { expected, found } => write!(f, "config root has the wrong shape: expected {}, found {}", expected, found),
+            FigConError::IndexOutOfBounds { index, len } => write!(f, "config index {} is out of bounds for {} elements", index, len),
+            FigConError::UnrepresentableNumber { key, value } => write!(f, "config value {} for \"{}\" can't be represented in JSON", value, key),
+            FigConError::Fragment { path, error } => write!(f, "config fragment {} failed: {}", path.display(), error),
+            FigConError::PatchFailed { index, reason } => write!(f, "config JSON Patch operation {} failed: {}", index, reason),
+            FigConError::Typed { path, error } if path.is_empty() => write!(f, "config doesn't fit the expected type: {}", error),
+            FigConError::Typed { path, error } => write!(f, "config doesn't fit the expected type at \"{}\": {}", path, error),
+            FigConError::NoFormatMatched { tried } => {
+                let tried: Vec<String> = tried.iter().map(Format::to_string).collect();
+                write!(f, "config couldn't be parsed as any known format (tried {})", tried.join(", "))
+            },
+            FigConError::UnsupportedFormat { extension } => write!(f, "config format \"{}\" isn't built in; load it through a custom codec", extension),
+            FigConError::ExtendsCycle { path } => write!(f, "config {} extends itself through its extends chain", path.display()),
+            FigConError::InvalidPath { path, reason } => write!(f, "config path \"{}\" is invalid: {}", path.display(), reason),
+            FigConError::PathIsDirectory { path } => write!(f, "config path {} is a directory, not a file", path.display()),
+            FigConError::ParentNotDirectory { path } => write!(f, "config path's parent {} is not a directory", path.display()),
+            FigConError::Codec(err) => write!(f, "config codec failed: {}", err),
+            FigConError::QuotaExceeded => write!(f, "config storage quota exceeded"),
+            #[cfg(feature = "binary")]
+            FigConError::Binary(err) => write!(f, "config binary encoding failed: {}", err),
+            #[cfg(feature = "testing")]
+            FigConError::RoundTrip { path } if path.is_empty() => write!(f, "config round trip changed the root value"),
+            #[cfg(feature = "testing")]
+            FigConError::RoundTrip { path } => write!(f, "config round trip changed the value at \"{}\"", path)
+        }
+    }
+}
+
+impl Error for FigConError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FigConError::Io(err) => Some(err),
+            FigConError::Parse { error, .. } => Some(error),
+            FigConError::Serialize(err) => Some(err),
+            FigConError::Typed { error, .. } => Some(error),
+            FigConError::Fragment { error, .. } => Some(error.as_ref()),
+            FigConError::Encoding(_) | FigConError::Codec(_) | FigConError::TooDeep { .. } | FigConError::WouldReplaceContainer { .. } | FigConError::QuotaExceeded => None,
+            FigConError::WrongRoot { .. } | FigConError::IndexOutOfBounds { .. } | FigConError::UnrepresentableNumber { .. } | FigConError::PathBlocked { .. } => None,
+            FigConError::InvalidPath { .. } | FigConError::PathIsDirectory { .. } | FigConError::ParentNotDirectory { .. } | FigConError::ExtendsCycle { .. } => None,
+            FigConError::PatchFailed { .. } | FigConError::NoFormatMatched { .. } | FigConError::UnsupportedFormat { .. } => None,
+            #[cfg(feature = "binary")]
+            FigConError::Binary(_) => None,
+            #[cfg(feature = "testing")]
+            FigConError::RoundTrip { .. } => None
+        }
+    }
+}
+
+impl FigConError {
+    /// # Parse Error
+    /// 
+    /// Wrap a JSON parse error together with a snippet of the source it failed in
+    pub(crate) fn parse(error: serde_json::Error, source: &[u8]) -> Self {
+        let snippet = snippet(&String::from_utf8_lossy(source), error.line(), error.column());
+        FigConError::Parse { error, snippet }
+    }
+}
+
+/// # Snippet
+/// 
+/// Quote a (1-based) line of the source with a caret under the (1-based) column, like:
+/// 
+/// ```text
+///   12 |   "port": 8080,,
+///      |                ^
+/// ```
+fn snippet(source: &str, line: usize, column: usize) -> String {
+    let Some(text) = line.checked_sub(1).and_then(|index| source.lines().nth(index)) else {
+        return String::new();
+    };
+    let gutter = line.to_string();
+    let offset = text.get(..column.saturating_sub(1)).map_or(column.saturating_sub(1), |prefix| prefix.chars().count());
+    format!("{} | {}\n{} | {}^", gutter, text, " ".repeat(gutter.len()), " ".repeat(offset))
+}
+
+impl From<io::Error> for FigConError {
+    fn from(err: io::Error) -> Self {
+        FigConError::Io(err)
+    }
+}
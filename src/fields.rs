@@ -0,0 +1,53 @@
+use serde::{
+    Deserialize,
+    Deserializer,
+    de::{
+        Error,
+        Visitor,
+        value
+    },
+    forward_to_deserialize_any
+};
+use std::cell::Cell;
+
+/// # Field Recorder
+///
+/// A deserializer that never produces a value; it only records the field list a struct asks for
+struct FieldRecorder<'a> {
+    fields: &'a Cell<Option<&'static [&'static str]>>
+}
+
+impl<'de> Deserializer<'de> for FieldRecorder<'_> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.fields.set(Some(fields));
+        Err(Error::custom("fields recorded"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// # Struct Fields
+///
+/// Return the serialized field names of a struct (after any `#[serde(rename)]`)
+///
+/// Returns None for types that don't deserialize as a plain struct, like maps or `#[serde(flatten)]` structs
+pub(crate) fn struct_fields<'de, T: Deserialize<'de>>() -> Option<&'static [&'static str]> {
+    let fields = Cell::new(None);
+    let _ = T::deserialize(FieldRecorder { fields: &fields });
+    fields.get()
+}
@@ -0,0 +1,319 @@
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    io
+};
+use crate::FigConError;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// # Format
+/// 
+/// The encoding a config file is stored in. The in-memory API is identical for every format
+/// 
+/// The binary formats need the `binary` feature. They mostly save on whitespace, quotes and punctuation:
+/// the example config.json in this repository is 237 bytes as pretty JSON and 151 bytes as either CBOR or MessagePack
+pub enum Format {
+    /// Pretty-printed JSON (The default)
+    #[default]
+    Json,
+    /// CBOR (RFC 8949), via ciborium
+    #[cfg(feature = "binary")]
+    Cbor,
+    /// MessagePack, via rmp-serde
+    #[cfg(feature = "binary")]
+    MsgPack
+}
+
+/// # Codec
+/// 
+/// A custom encoding for a config's bytes, so downstream crates can store configs in formats this crate doesn't know
+/// 
+/// Register one with `FigCon::with_codec()` or `FigCon::load_with_codec()`. Every built-in `Format` is a codec too.
+/// Failures can be any `FigConError`; `FigConError::Codec` carries a plain message
+/// 
+/// TOML and YAML aren't built-in formats (the extension-picking loaders reject `.toml`, `.yaml` and `.yml` files),
+/// so a codec wrapping a crate like `toml` or `serde_yaml` is the way to read them
+pub trait Codec: Send + Sync {
+    /// # Encode
+    /// 
+    /// Serialize a value into bytes
+    fn encode(&self, value: &Value) -> Result<Vec<u8>, FigConError>;
+
+    /// # Decode
+    /// 
+    /// Deserialize a value from bytes
+    fn decode(&self, bytes: &[u8]) -> Result<Value, FigConError>;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// # Display Format
+/// 
+/// How `{}` renders a config, chosen with `FigCon::with_display_format()`. `{:#}` always pretty-prints
+pub enum DisplayFormat {
+    /// Indented over several lines, like the saved file (The default)
+    #[default]
+    Pretty,
+    /// On a single line, for logs
+    Compact
+}
+
+impl Format {
+    /// # Enabled
+    /// 
+    /// Every format compiled in, in the order `FigCon::load_auto()` tries them
+    pub(crate) const ENABLED: &'static [Format] = &[
+        Format::Json,
+        #[cfg(feature = "binary")]
+        Format::Cbor,
+        #[cfg(feature = "binary")]
+        Format::MsgPack
+    ];
+
+    /// # From Extension
+    /// 
+    /// The enabled format a file extension stands for, if any (`json`, `cbor`, `msgpack` or `mpk`, case-insensitive)
+    pub(crate) fn from_extension(extension: &str) -> Option<Format> {
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            #[cfg(feature = "binary")]
+            "cbor" => Some(Format::Cbor),
+            #[cfg(feature = "binary")]
+            "msgpack" | "mpk" => Some(Format::MsgPack),
+            _ => None
+        }
+    }
+
+    /// # Check Extension
+    /// 
+    /// Fail with `FigConError::UnsupportedFormat` for an extension naming a well-known format that isn't built in
+    /// (`toml`, `yaml` or `yml`), rather than reading such a file as one of the enabled formats
+    pub(crate) fn check_extension(extension: &str) -> Result<(), FigConError> {
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" | "yaml" | "yml" => Err(FigConError::UnsupportedFormat { extension: extension.to_owned() }),
+            _ => Ok(())
+        }
+    }
+
+    /// # Encode
+    /// 
+    /// Serialize a value into this format
+    pub(crate) fn encode(&self, value: &Value) -> Result<Vec<u8>, FigConError> {
+        let mut bytes = Vec::new();
+        self.encode_into(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// # Encode Into
+    /// 
+    /// Serialize a value into this format, streaming it to a writer
+    pub(crate) fn encode_into<W: io::Write>(&self, value: &Value, writer: &mut W) -> Result<(), FigConError> {
+        match self {
+            Format::Json => serde_json::to_writer_pretty(writer, value).map_err(FigConError::Serialize),
+            #[cfg(feature = "binary")]
+            Format::Cbor => ciborium::into_writer(value, writer).map_err(|err| FigConError::Binary(err.to_string())),
+            #[cfg(feature = "binary")]
+            Format::MsgPack => rmp_serde::encode::write_named(writer, value).map_err(|err| FigConError::Binary(err.to_string()))
+        }
+    }
+
+    /// # Encode Text
+    /// 
+    /// Serialize a value as this format's text form, pretty-printed or compact
+    /// 
+    /// The binary formats have no text form, so they fall back to JSON (the model every format shares)
+    pub(crate) fn encode_text(&self, value: &Value, pretty: bool) -> Result<String, FigConError> {
+        let text = if pretty { serde_json::to_string_pretty(value) } else { serde_json::to_string(value) };
+        text.map_err(FigConError::Serialize)
+    }
+
+    /// # Decode
+    /// 
+    /// Deserialize a value stored in this format, failing with `FigConError::TooDeep` past `FigCon::MAX_DEPTH`
+    pub(crate) fn decode(&self, bytes: &[u8]) -> Result<Value, FigConError> {
+        let value = match self {
+            Format::Json => {
+                let text = to_utf8(bytes)?;
+                serde_json::from_slice(&text).map_err(|err| FigConError::parse(err, &text))
+            },
+            #[cfg(feature = "binary")]
+            Format::Cbor => ciborium::from_reader(bytes).map_err(|err| FigConError::Binary(err.to_string())),
+            #[cfg(feature = "binary")]
+            Format::MsgPack => rmp_serde::from_slice(bytes).map_err(|err| FigConError::Binary(err.to_string()))
+        }?;
+        crate::check_depth(&value, 0)?; // the binary decoders allow deeper nesting than serde_json does
+        Ok(value)
+    }
+}
+
+/// # To UTF-8
+/// 
+/// Strip a leading UTF-8 byte order mark (Common in files saved on Windows), and transcode UTF-16 text
+/// marked with a little or big endian BOM to UTF-8
+/// 
+/// Fails with `FigConError::Encoding` if UTF-16 text is truncated or holds unpaired surrogates
+fn to_utf8(bytes: &[u8]) -> Result<Cow<'_, [u8]>, FigConError> {
+    let (units, little_endian) = match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => return Ok(Cow::Borrowed(rest)),
+        [0xFF, 0xFE, rest @ ..] => (rest, true),
+        [0xFE, 0xFF, rest @ ..] => (rest, false),
+        _ => return Ok(Cow::Borrowed(bytes))
+    };
+    if units.len() % 2 != 0 {
+        return Err(FigConError::Encoding("UTF-16 config has an odd number of bytes".to_owned()));
+    }
+    let units: Vec<u16> = units.chunks_exact(2).map(|pair| {
+        if little_endian { u16::from_le_bytes([pair[0], pair[1]]) } else { u16::from_be_bytes([pair[0], pair[1]]) }
+    }).collect();
+    String::from_utf16(&units)
+        .map(|text| Cow::Owned(text.into_bytes()))
+        .map_err(|_| FigConError::Encoding("UTF-16 config contains invalid surrogates".to_owned()))
+}
+
+impl Codec for Format {
+    fn encode(&self, value: &Value) -> Result<Vec<u8>, FigConError> {
+        Format::encode(self, value)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Value, FigConError> {
+        Format::decode(self, bytes)
+    }
+}
+
+/// # Depth Limited
+/// 
+/// A custom codec whose decoded values are held to `FigCon::MAX_DEPTH`, like the built-in formats
+pub(crate) struct DepthLimited(pub(crate) Box<dyn Codec>);
+
+impl Codec for DepthLimited {
+    fn encode(&self, value: &Value) -> Result<Vec<u8>, FigConError> {
+        self.0.encode(value)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Value, FigConError> {
+        let value = self.0.decode(bytes)?;
+        crate::check_depth(&value, 0)?;
+        Ok(value)
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Json => write!(f, "JSON"),
+            #[cfg(feature = "binary")]
+            Format::Cbor => write!(f, "CBOR"),
+            #[cfg(feature = "binary")]
+            Format::MsgPack => write!(f, "MessagePack")
+        }
+    }
+}
+
+#[derive(Default)]
+/// # Byte Counter
+/// 
+/// A writer that throws the bytes away and only counts them, for measuring serialized output without buffering it
+pub(crate) struct ByteCounter(pub(crate) usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0 += bytes.len();
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use crate::{FigCon, FigConError};
+    use super::{Format, to_utf8};
+
+    fn sample() -> Value {
+        json!({
+            "name": "figcon",
+            "port": 8080,
+            "offset": -42,
+            "ratio": 0.25,
+            "enabled": true,
+            "missing": null,
+            "tags": ["a", "ü", "🦀"],
+            "nested": { "empty": {}, "list": [], "deep": { "level": [1, { "two": 2.5 }] } }
+        })
+    }
+
+    /// Save the sample in `format`, load it back and compare
+    fn round_trip(format: Format, file: &str) {
+        let path = std::env::temp_dir().join(format!("figcon-{}-{}", std::process::id(), file));
+        let conf = FigCon::from_parts(sample(), path.clone()).with_format(format);
+        conf.try_save().unwrap();
+        let reloaded = FigCon::load_with_format(path.clone(), format);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reloaded.live_config, sample());
+        assert_eq!(reloaded.format, format);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(Format::Json, "round-trip.json");
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn cbor_round_trip() {
+        round_trip(Format::Cbor, "round-trip.cbor");
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn msgpack_round_trip() {
+        round_trip(Format::MsgPack, "round-trip.msgpack");
+    }
+
+    const TEXT: &str = "{\"name\": \"ü🦀\"}";
+
+    fn utf16(little_endian: bool) -> Vec<u8> {
+        let bom: &[u8] = if little_endian { &[0xFF, 0xFE] } else { &[0xFE, 0xFF] };
+        bom.iter().copied().chain(TEXT.encode_utf16().flat_map(|unit| {
+            if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() }
+        })).collect()
+    }
+
+    #[test]
+    fn utf8_without_bom_is_borrowed_unchanged() {
+        let decoded = to_utf8(TEXT.as_bytes()).unwrap();
+        assert!(matches!(decoded, std::borrow::Cow::Borrowed(_)));
+        assert_eq!(&*decoded, TEXT.as_bytes());
+    }
+
+    #[test]
+    fn utf8_bom_is_stripped() {
+        let bytes = [&[0xEF, 0xBB, 0xBF], TEXT.as_bytes()].concat();
+        assert_eq!(&*to_utf8(&bytes).unwrap(), TEXT.as_bytes());
+        assert_eq!(Format::Json.decode(&bytes).unwrap(), json!({ "name": "ü🦀" }));
+    }
+
+    #[test]
+    fn utf16_le_bom_is_transcoded() {
+        assert_eq!(&*to_utf8(&utf16(true)).unwrap(), TEXT.as_bytes());
+        assert_eq!(Format::Json.decode(&utf16(true)).unwrap(), json!({ "name": "ü🦀" }));
+    }
+
+    #[test]
+    fn utf16_be_bom_is_transcoded() {
+        assert_eq!(&*to_utf8(&utf16(false)).unwrap(), TEXT.as_bytes());
+        assert_eq!(Format::Json.decode(&utf16(false)).unwrap(), json!({ "name": "ü🦀" }));
+    }
+
+    #[test]
+    fn broken_utf16_is_an_encoding_error() {
+        let mut truncated = utf16(true);
+        truncated.pop();
+        assert!(matches!(to_utf8(&truncated), Err(FigConError::Encoding(_))));
+        let unpaired = [0xFF, 0xFE, 0x00, 0xD8]; // a lone high surrogate
+        assert!(matches!(to_utf8(&unpaired), Err(FigConError::Encoding(_))));
+    }
+}
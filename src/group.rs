@@ -0,0 +1,183 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    slice
+};
+use crate::{FigCon, FigConError, Staged, is_stdio};
+
+#[derive(Clone, Debug, Default)]
+/// # FigCon Group
+///
+/// Several configs that have to stay consistent with each other (like a base file and its overrides), saved together with `.save_all()`
+pub struct FigConGroup {
+    configs: Vec<FigCon>
+}
+
+impl FigConGroup {
+    pub fn new(configs: Vec<FigCon>) -> Self {
+        FigConGroup { configs }
+    }
+
+    /// # Push
+    ///
+    /// Add a config to the group, after the ones already in it
+    pub fn push(&mut self, conf: FigCon) {
+        self.configs.push(conf);
+    }
+
+    /// # Get
+    ///
+    /// The config at this position in the group
+    pub fn get(&self, index: usize) -> Option<&FigCon> {
+        self.configs.get(index)
+    }
+
+    /// # Get (Mutable)
+    ///
+    /// The config at this position in the group, for editing
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut FigCon> {
+        self.configs.get_mut(index)
+    }
+
+    /// # Iter
+    ///
+    /// Every config in the group, in order
+    pub fn iter(&self) -> slice::Iter<'_, FigCon> {
+        self.configs.iter()
+    }
+
+    /// # Length
+    ///
+    /// How many configs the group holds
+    pub fn len(&self) -> usize {
+        self.configs.len()
+    }
+
+    /// # Is Empty
+    ///
+    /// Whether the group holds no configs
+    pub fn is_empty(&self) -> bool {
+        self.configs.is_empty()
+    }
+
+    /// # Save All
+    ///
+    /// Save every config in the group, in three steps: serialize them all, write each to a temporary file beside it
+    /// (`config.json.<pid>.<n>.tmp`), and only then rename the temporary files over the configs one after another
+    ///
+    /// A failure while serializing or staging leaves every config file untouched and removes the temporary files.
+    /// Renames are quick, so a crash leaves the files inconsistent only in the short window between the first and last rename,
+    /// but that window can't be closed: no common filesystem renames several files atomically, so this is best-effort.
+    /// If a rename fails, the configs before it are saved, the rest keep their old contents and the error is returned.
+    /// Configs on custom storage or stdout can't be staged and are written directly in their turn
+    /// 
+    /// Two configs saving to the same file (through symlinks or differently spelled paths too) would overwrite each other,
+    /// so such a group fails with `FigConError::InvalidPath` before anything is written
+    pub fn save_all(&self) -> Result<(), FigConError> {
+        self.check_targets()?;
+        let mut staged = Vec::with_capacity(self.configs.len());
+        for conf in &self.configs {
+            staged.push(conf.to_bytes()?);
+        }
+        let mut temps: Vec<Staged> = Vec::with_capacity(self.configs.len());
+        for (conf, bytes) in self.configs.iter().zip(&staged) {
+            match conf.stage(bytes) {
+                Ok(temp) => temps.push(temp),
+                Err(err) => {
+                    discard(&temps);
+                    return Err(err);
+                }
+            }
+        }
+        for (index, conf) in self.configs.iter().enumerate() {
+            let written = match &temps[index] {
+                Staged::File(temp) => conf.commit_staged(temp, staged[index].len()),
+                Staged::Unchanged => Ok(0),
+                Staged::Direct => conf.write_out_retrying()
+            };
+            if let Err(err) = conf.finish_save(written) {
+                discard(&temps[index..]);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// # Check Targets
+    ///
+    /// Make sure no two file-backed configs in the group save to the same file
+    fn check_targets(&self) -> Result<(), FigConError> {
+        let mut seen: Vec<PathBuf> = Vec::with_capacity(self.configs.len());
+        for conf in self.configs.iter().filter(|conf| conf.storage.is_none() && !is_stdio(&conf.location)) {
+            let target = canonical(conf.write_target()?);
+            if seen.contains(&target) {
+                return Err(FigConError::InvalidPath { path: conf.location.clone(), reason: "another config in the group saves to the same file".to_owned() });
+            }
+            seen.push(target);
+        }
+        Ok(())
+    }
+}
+
+/// # Canonical
+///
+/// Spell a file's path the same way however it was given, by resolving its directory (The file itself may not exist yet)
+fn canonical(target: PathBuf) -> PathBuf {
+    let (Some(parent), Some(name)) = (target.parent(), target.file_name()) else { return target; };
+    let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+    fs::canonicalize(parent).map_or(target.clone(), |parent| parent.join(name))
+}
+
+/// # Discard
+///
+/// Remove staged temporary files that won't be renamed into place
+fn discard(temps: &[Staged]) {
+    for staged in temps {
+        if let Staged::File(temp) = staged {
+            let _ = fs::remove_file(temp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::{fs, path::PathBuf};
+    use crate::{FigCon, FigConError};
+    use super::FigConGroup;
+
+    #[test]
+    fn refuses_members_saving_to_the_same_file() {
+        let dir = std::env::temp_dir().join(format!("figcon-{}-group", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("shared.json");
+        let respelled = dir.join(".").join("shared.json");
+        let group = FigConGroup::new(vec![
+            FigCon::from_parts(json!({ "a": 1 }), path.clone()),
+            FigCon::from_parts(json!({ "a": 2 }), respelled)
+        ]);
+        let result = group.save_all();
+        let entries: Vec<PathBuf> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(result, Err(FigConError::InvalidPath { .. })));
+        assert!(entries.is_empty()); // nothing staged or saved
+    }
+
+    #[test]
+    fn saves_every_member() {
+        let dir = std::env::temp_dir().join(format!("figcon-{}-group-ok", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let group = FigConGroup::new(vec![
+            FigCon::from_parts(json!({ "a": 1 }), dir.join("base.json")),
+            FigCon::from_parts(json!({ "b": 2 }), dir.join("local.json"))
+        ]);
+        group.save_all().unwrap();
+        let base = FigCon::try_load(dir.join("base.json")).unwrap();
+        let local = FigCon::try_load(dir.join("local.json")).unwrap();
+        let entries = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(base.get_key_st("a"), Some(&json!(1)));
+        assert_eq!(local.get_key_st("b"), Some(&json!(2)));
+        assert_eq!(entries, 2);
+    }
+}
@@ -0,0 +1,79 @@
+use serde_json::{Value, json};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path
+};
+use crate::ValueExtensions;
+
+/// # Append Set
+///
+/// Record a `set` operation at the end of the journal
+pub(crate) fn append_set(path: &Path, key: &str, value: &Value) -> io::Result<()> {
+    append(path, &[set_record(key, value)])
+}
+
+/// # Append Delete
+///
+/// Record a `del` operation at the end of the journal
+pub(crate) fn append_del(path: &Path, key: &str) -> io::Result<()> {
+    append(path, &[del_record(key)])
+}
+
+/// # Set Record
+///
+/// The journal record of a `set` operation
+pub(crate) fn set_record(key: &str, value: &Value) -> Value {
+    json!({ "op": "set", "key": key, "value": value })
+}
+
+/// # Delete Record
+///
+/// The journal record of a `del` operation
+pub(crate) fn del_record(key: &str) -> Value {
+    json!({ "op": "del", "key": key })
+}
+
+/// # Append
+///
+/// Write records as JSON lines, all in a single write call so a crash can only ever tear the last line
+pub(crate) fn append(path: &Path, records: &[Value]) -> io::Result<()> {
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&record.to_string());
+        lines.push('\n');
+    }
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(lines.as_bytes())
+}
+
+/// # Replay
+///
+/// Apply every record in the journal to a value, in order
+///
+/// A missing journal replays nothing. Lines that don't parse (a torn write from a crash) are skipped
+pub(crate) fn replay(path: &Path, target: &mut Value) -> io::Result<()> {
+    let journal = match fs::read_to_string(path) {
+        Ok(journal) => journal,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err)
+    };
+    for record in journal.lines().filter_map(|line| serde_json::from_str::<Value>(line).ok()) {
+        let Some(key) = record.get_key_st("key").and_then(Value::as_str) else { continue; };
+        match record.get_key_st("op").and_then(Value::as_str) {
+            Some("set") => target.set_key_st(key, record.get_key_st("value").cloned().unwrap_or(Value::Null)),
+            Some("del") => target.remove_key_st(key),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// # Truncate
+///
+/// Empty the journal once its records have been folded into the main file
+pub(crate) fn truncate(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(())
+    }
+}
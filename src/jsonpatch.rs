@@ -0,0 +1,388 @@
+use serde_json::{Value, json};
+use crate::{FigConError, MAX_DEPTH};
+
+/// # Escape
+///
+/// Escape a key for use as a JSON Pointer segment (RFC 6901): `~` becomes `~0` and `/` becomes `~1`
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// # Diff Into
+///
+/// Push the RFC 6902 operations that turn `old` into `new`, both found at the JSON Pointer `pointer`
+///
+/// Objects are compared key by key and arrays index by index, with extra elements removed from the end
+/// or appended, so an insertion in the middle of an array shows up as replacements rather than a single `add`
+///
+/// `depth` is how many containers deep the values sit; containers from `MAX_DEPTH` down are replaced whole
+pub(crate) fn diff_into(pointer: &str, old: &Value, new: &Value, ops: &mut Vec<Value>, depth: usize) {
+    match (old, new) {
+        (Value::Object(old_object), Value::Object(new_object)) if depth < MAX_DEPTH => {
+            for (key, old_value) in old_object {
+                let path = format!("{}/{}", pointer, escape(key));
+                match new_object.get(key) {
+                    Some(new_value) => diff_into(&path, old_value, new_value, ops, depth + 1),
+                    None => ops.push(json!({ "op": "remove", "path": path }))
+                }
+            }
+            for (key, new_value) in new_object.iter().filter(|(key, _)| !old_object.contains_key(*key)) {
+                ops.push(json!({ "op": "add", "path": format!("{}/{}", pointer, escape(key)), "value": new_value }));
+            }
+        },
+        (Value::Array(old_items), Value::Array(new_items)) if depth < MAX_DEPTH => {
+            for (index, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+                diff_into(&format!("{}/{}", pointer, index), old_item, new_item, ops, depth + 1);
+            }
+            for index in (new_items.len()..old_items.len()).rev() {
+                ops.push(json!({ "op": "remove", "path": format!("{}/{}", pointer, index) }));
+            }
+            for (index, new_item) in new_items.iter().enumerate().skip(old_items.len()) {
+                ops.push(json!({ "op": "add", "path": format!("{}/{}", pointer, index), "value": new_item }));
+            }
+        },
+        _ if old != new => ops.push(json!({ "op": "replace", "path": pointer, "value": new })),
+        _ => {}
+    }
+}
+
+/// # Apply
+///
+/// Run an RFC 6902 patch (`add`, `remove`, `replace`, `move`, `copy` and `test`) against a value, in order
+///
+/// Stops at the first operation that fails, with its index, leaving the value partly patched (callers patch a copy)
+pub(crate) fn apply(target: &mut Value, patch: &Value) -> Result<(), FigConError> {
+    let Value::Array(ops) = patch else {
+        return Err(FigConError::PatchFailed { index: 0, reason: "the patch isn't an array of operations".to_owned() });
+    };
+    for (index, op) in ops.iter().enumerate() {
+        apply_op(target, op).map_err(|reason| FigConError::PatchFailed { index, reason })?;
+    }
+    Ok(())
+}
+
+/// # Apply Operation
+///
+/// Run one patch operation, describing why it failed
+fn apply_op(target: &mut Value, op: &Value) -> Result<(), String> {
+    let member = |name: &str| op.get(name).ok_or_else(|| format!("missing \"{}\"", name));
+    let pointer = |name: &str| member(name)?.as_str().ok_or_else(|| format!("\"{}\" isn't a string", name)).and_then(parse_pointer);
+    let path = pointer("path")?;
+    match member("op")?.as_str() {
+        Some("add") => add(target, &path, member("value")?.clone()),
+        Some("remove") => remove(target, &path).map(|_| ()),
+        Some("replace") => {
+            let value = member("value")?.clone();
+            *get_mut(target, &path).ok_or_else(|| missing(&path))? = value;
+            Ok(())
+        },
+        Some("move") => {
+            let from = pointer("from")?;
+            if path.len() > from.len() && path.starts_with(&from) {
+                return Err("can't move a value into one of its own children".to_owned());
+            }
+            let value = remove(target, &from)?;
+            add(target, &path, value)
+        },
+        Some("copy") => {
+            let from = pointer("from")?;
+            let value = get(target, &from).ok_or_else(|| missing(&from))?.clone();
+            add(target, &path, value)
+        },
+        Some("test") => match get(target, &path) {
+            Some(value) if value == member("value")? => Ok(()),
+            Some(_) => Err(format!("test failed at \"{}\"", display(&path))),
+            None => Err(missing(&path))
+        },
+        Some(other) => Err(format!("unknown operation \"{}\"", other)),
+        None => Err("\"op\" isn't a string".to_owned())
+    }
+}
+
+/// # Parse Pointer
+///
+/// Split a JSON Pointer into its unescaped segments (The empty pointer is the whole value)
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() { return Ok(Vec::new()); }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("\"{}\" isn't a JSON Pointer", pointer));
+    };
+    Ok(rest.split('/').map(|segment| segment.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+/// # Display
+///
+/// Write segments back out as a JSON Pointer, for error messages
+fn display(path: &[String]) -> String {
+    path.iter().map(|segment| format!("/{}", escape(segment))).collect()
+}
+
+/// # Missing
+///
+/// The error for a pointer that leads nowhere
+fn missing(path: &[String]) -> String {
+    format!("nothing at \"{}\"", display(path))
+}
+
+/// # Array Index
+///
+/// Parse an array index segment, which must be plain digits without leading zeros
+fn array_index(segment: &str) -> Option<usize> {
+    let digits = !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit());
+    if !digits || (segment.len() > 1 && segment.starts_with('0')) { return None; }
+    segment.parse().ok()
+}
+
+/// # Get
+///
+/// Follow pointer segments through objects and arrays
+fn get<'a>(root: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(root, |current, segment| match current {
+        Value::Object(object) => object.get(segment),
+        Value::Array(items) => items.get(array_index(segment)?),
+        _ => None
+    })
+}
+
+/// # Get (Mutable)
+///
+/// Follow pointer segments through objects and arrays, for editing what they point at
+fn get_mut<'a>(root: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    path.iter().try_fold(root, |current, segment| match current {
+        Value::Object(object) => object.get_mut(segment),
+        Value::Array(items) => items.get_mut(array_index(segment)?),
+        _ => None
+    })
+}
+
+/// # Add
+///
+/// Insert a value: a member of an object is set, an array element is inserted before the index (`-` appends),
+/// and the empty pointer replaces the whole value
+fn add(root: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let Some((last, parent)) = path.split_last() else {
+        *root = value;
+        return Ok(());
+    };
+    match get_mut(root, parent).ok_or_else(|| missing(parent))? {
+        Value::Object(object) => {
+            object.insert(last.clone(), value);
+            Ok(())
+        },
+        Value::Array(items) => {
+            let index = if last == "-" { Some(items.len()) } else { array_index(last) };
+            match index {
+                Some(index) if index <= items.len() => {
+                    items.insert(index, value);
+                    Ok(())
+                },
+                _ => Err(format!("index out of bounds at \"{}\"", display(path)))
+            }
+        },
+        _ => Err(format!("\"{}\" isn't an object or array", display(parent)))
+    }
+}
+
+/// # Remove
+///
+/// Take a value out of its object or array, shifting later array elements down
+fn remove(root: &mut Value, path: &[String]) -> Result<Value, String> {
+    let Some((last, parent)) = path.split_last() else {
+        return Err("can't remove the whole config".to_owned());
+    };
+    let removed = match get_mut(root, parent) {
+        Some(Value::Object(object)) => object.remove(last),
+        Some(Value::Array(items)) => array_index(last).filter(|index| *index < items.len()).map(|index| items.remove(index)),
+        _ => None
+    };
+    removed.ok_or_else(|| missing(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use crate::FigConError;
+    use super::{apply, diff_into};
+
+    fn diff(old: &Value, new: &Value) -> Value {
+        let mut ops = Vec::new();
+        diff_into("", old, new, &mut ops, 0);
+        Value::Array(ops)
+    }
+
+    /// Diff two documents, apply the diff to the first and check it comes out as the second
+    fn assert_round_trip(old: Value, new: Value) {
+        let patch = diff(&old, &new);
+        let mut patched = old.clone();
+        apply(&mut patched, &patch).unwrap_or_else(|error| panic!("{} applying {}", error, patch));
+        assert_eq!(patched, new, "patch {} from {}", patch, old);
+    }
+
+    /// Apply a patch to a document, expecting it to succeed with `expected`
+    fn assert_applies(doc: Value, patch: Value, expected: Value) {
+        let mut patched = doc;
+        apply(&mut patched, &patch).unwrap_or_else(|error| panic!("{} applying {}", error, patch));
+        assert_eq!(patched, expected);
+    }
+
+    /// Apply a patch to a document, expecting operation `index` to fail
+    fn assert_rejected(doc: Value, patch: Value, index: usize) {
+        match apply(&mut doc.clone(), &patch) {
+            Err(FigConError::PatchFailed { index: failed, .. }) => assert_eq!(failed, index, "patch {}", patch),
+            other => panic!("expected {} to be rejected, got {:?}", patch, other)
+        }
+    }
+
+    // RFC 6902 Appendix A examples whose patch is the minimal one, so the diff has to match it exactly
+
+    #[test]
+    fn appendix_a1_adding_an_object_member() {
+        assert_eq!(diff(&json!({ "foo": "bar" }), &json!({ "baz": "qux", "foo": "bar" })), json!([
+            { "op": "add", "path": "/baz", "value": "qux" }
+        ]));
+    }
+
+    #[test]
+    fn appendix_a3_removing_an_object_member() {
+        assert_eq!(diff(&json!({ "baz": "qux", "foo": "bar" }), &json!({ "foo": "bar" })), json!([
+            { "op": "remove", "path": "/baz" }
+        ]));
+    }
+
+    #[test]
+    fn appendix_a5_replacing_a_value() {
+        assert_eq!(diff(&json!({ "baz": "qux", "foo": "bar" }), &json!({ "baz": "boo", "foo": "bar" })), json!([
+            { "op": "replace", "path": "/baz", "value": "boo" }
+        ]));
+    }
+
+    #[test]
+    fn appendix_a10_adding_a_nested_member_object() {
+        assert_eq!(diff(&json!({ "foo": "bar" }), &json!({ "foo": "bar", "child": { "grandchild": {} } })), json!([
+            { "op": "add", "path": "/child", "value": { "grandchild": {} } }
+        ]));
+    }
+
+    #[test]
+    fn appendix_a14_escapes_pointer_segments() {
+        assert_eq!(diff(&json!({ "/": 9, "~1": 10 }), &json!({ "/": 9, "~1": 11 })), json!([
+            { "op": "replace", "path": "/~01", "value": 11 }
+        ]));
+        assert_eq!(diff(&json!({ "/": 9 }), &json!({ "/": 10 })), json!([
+            { "op": "replace", "path": "/~1", "value": 10 }
+        ]));
+    }
+
+    #[test]
+    fn appendix_documents_round_trip() {
+        assert_round_trip(json!({ "foo": "bar" }), json!({ "baz": "qux", "foo": "bar" })); // A.1
+        assert_round_trip(json!({ "foo": ["bar", "baz"] }), json!({ "foo": ["bar", "qux", "baz"] })); // A.2
+        assert_round_trip(json!({ "baz": "qux", "foo": "bar" }), json!({ "foo": "bar" })); // A.3
+        assert_round_trip(json!({ "foo": ["bar", "qux", "baz"] }), json!({ "foo": ["bar", "baz"] })); // A.4
+        assert_round_trip(json!({ "baz": "qux", "foo": "bar" }), json!({ "baz": "boo", "foo": "bar" })); // A.5
+        assert_round_trip( // A.6
+            json!({ "foo": { "bar": "baz", "waldo": "fred" }, "qux": { "corge": "grault" } }),
+            json!({ "foo": { "bar": "baz" }, "qux": { "corge": "grault", "thud": "fred" } })
+        );
+        assert_round_trip(json!({ "foo": ["all", "grass", "cows", "eat"] }), json!({ "foo": ["all", "cows", "eat", "grass"] })); // A.7
+        assert_round_trip(json!({ "foo": "bar" }), json!({ "foo": "bar", "child": { "grandchild": {} } })); // A.10
+        assert_round_trip(json!({ "/": 9, "~1": 10 }), json!({ "/": 9, "~1": "10" })); // A.15
+        assert_round_trip(json!({ "foo": ["bar"] }), json!({ "foo": ["bar", ["abc", "def"]] })); // A.16
+    }
+
+    #[test]
+    fn reshaped_documents_round_trip() {
+        assert_round_trip(json!({}), json!({}));
+        assert_round_trip(json!(1), json!({ "a": 1 }));
+        assert_round_trip(json!({ "a": [1, 2, 3, 4] }), json!({ "a": [] }));
+        assert_round_trip(json!({ "a": [] }), json!({ "a": [{ "b": null }, [1], "x"] }));
+        assert_round_trip(json!({ "a": { "b": [1, { "c": true }] } }), json!({ "a": { "b": [1, { "c": false, "d": [] }] } }));
+        assert_round_trip(json!({ "a": { "b": 1 } }), json!({ "a": [1] }));
+        assert_round_trip(json!([{ "": "empty key" }, "~0/"]), json!([{ "": "still empty" }]));
+    }
+
+    // RFC 6902 Appendix A, applied (A.13 is left out: serde_json keeps only the last of a duplicated "op" member)
+
+    #[test]
+    fn appendix_applies() {
+        assert_applies(json!({ "foo": "bar" }), json!([{ "op": "add", "path": "/baz", "value": "qux" }]), json!({ "baz": "qux", "foo": "bar" })); // A.1
+        assert_applies(json!({ "foo": ["bar", "baz"] }), json!([{ "op": "add", "path": "/foo/1", "value": "qux" }]), json!({ "foo": ["bar", "qux", "baz"] })); // A.2
+        assert_applies(json!({ "baz": "qux", "foo": "bar" }), json!([{ "op": "remove", "path": "/baz" }]), json!({ "foo": "bar" })); // A.3
+        assert_applies(json!({ "foo": ["bar", "qux", "baz"] }), json!([{ "op": "remove", "path": "/foo/1" }]), json!({ "foo": ["bar", "baz"] })); // A.4
+        assert_applies(json!({ "baz": "qux", "foo": "bar" }), json!([{ "op": "replace", "path": "/baz", "value": "boo" }]), json!({ "baz": "boo", "foo": "bar" })); // A.5
+        assert_applies( // A.6
+            json!({ "foo": { "bar": "baz", "waldo": "fred" }, "qux": { "corge": "grault" } }),
+            json!([{ "op": "move", "from": "/foo/waldo", "path": "/qux/thud" }]),
+            json!({ "foo": { "bar": "baz" }, "qux": { "corge": "grault", "thud": "fred" } })
+        );
+        assert_applies( // A.7
+            json!({ "foo": ["all", "grass", "cows", "eat"] }),
+            json!([{ "op": "move", "from": "/foo/1", "path": "/foo/3" }]),
+            json!({ "foo": ["all", "cows", "eat", "grass"] })
+        );
+        assert_applies( // A.8
+            json!({ "baz": "qux", "foo": ["a", 2, "c"] }),
+            json!([{ "op": "test", "path": "/baz", "value": "qux" }, { "op": "test", "path": "/foo/1", "value": 2 }]),
+            json!({ "baz": "qux", "foo": ["a", 2, "c"] })
+        );
+        assert_applies( // A.10
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/child", "value": { "grandchild": {} } }]),
+            json!({ "foo": "bar", "child": { "grandchild": {} } })
+        );
+        assert_applies( // A.11
+            json!({ "foo": "bar" }),
+            json!([{ "op": "add", "path": "/baz", "value": "qux", "xyz": 123 }]),
+            json!({ "foo": "bar", "baz": "qux" })
+        );
+        assert_applies(json!({ "/": 9, "~1": 10 }), json!([{ "op": "test", "path": "/~01", "value": 10 }]), json!({ "/": 9, "~1": 10 })); // A.14
+        assert_applies( // A.16
+            json!({ "foo": ["bar"] }),
+            json!([{ "op": "add", "path": "/foo/-", "value": ["abc", "def"] }]),
+            json!({ "foo": ["bar", ["abc", "def"]] })
+        );
+    }
+
+    #[test]
+    fn appendix_rejects() {
+        assert_rejected(json!({ "baz": "qux" }), json!([{ "op": "test", "path": "/baz", "value": "bar" }]), 0); // A.9
+        assert_rejected(json!({ "foo": "bar" }), json!([{ "op": "add", "path": "/baz/bat", "value": "qux" }]), 0); // A.12
+        assert_rejected(json!({ "/": 9, "~1": 10 }), json!([{ "op": "test", "path": "/~01", "value": "10" }]), 0); // A.15
+    }
+
+    // Cases from the json-patch test suite the RFC's examples don't cover
+
+    #[test]
+    fn spec_edge_cases_apply() {
+        assert_applies(json!({ "foo": 1 }), json!([{ "op": "add", "path": "", "value": [1] }]), json!([1]));
+        assert_applies(json!([]), json!([{ "op": "add", "path": "/0", "value": "x" }]), json!(["x"]));
+        assert_applies(json!(["a"]), json!([{ "op": "add", "path": "/1", "value": "b" }]), json!(["a", "b"]));
+        assert_applies(json!({ "": 1 }), json!([{ "op": "replace", "path": "/", "value": 2 }]), json!({ "": 2 }));
+        assert_applies(json!({ "foo": null }), json!([{ "op": "test", "path": "/foo", "value": null }]), json!({ "foo": null }));
+        assert_applies(json!({ "foo": 1 }), json!([{ "op": "move", "from": "/foo", "path": "/foo" }]), json!({ "foo": 1 }));
+        assert_applies(json!({ "foo": [1, 2] }), json!([{ "op": "copy", "from": "/foo/0", "path": "/foo/-" }]), json!({ "foo": [1, 2, 1] }));
+        assert_applies(
+            json!({ "foo": { "bar": [1, 2] } }),
+            json!([{ "op": "test", "path": "/foo", "value": { "bar": [1, 2] } }, { "op": "remove", "path": "/foo/bar/0" }]),
+            json!({ "foo": { "bar": [2] } })
+        );
+    }
+
+    #[test]
+    fn spec_edge_cases_reject() {
+        assert_rejected(json!({ "bar": [1, 2] }), json!([{ "op": "add", "path": "/bar/8", "value": "5" }]), 0); // out of bounds
+        assert_rejected(json!({ "bar": [1, 2] }), json!([{ "op": "add", "path": "/bar/-1", "value": "5" }]), 0);
+        assert_rejected(json!({ "foo": [1, 2] }), json!([{ "op": "remove", "path": "/foo/01" }]), 0); // leading zero
+        assert_rejected(json!({ "foo": [1, 2] }), json!([{ "op": "remove", "path": "/foo/-" }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "remove", "path": "/bar" }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "replace", "path": "/bar", "value": 2 }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "spam", "path": "/foo" }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "add", "value": 2 }]), 0); // missing path
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "add", "path": "/bar" }]), 0); // missing value
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "add", "path": "bar", "value": 2 }]), 0); // not a pointer
+        assert_rejected(json!({ "foo": { "bar": 1 } }), json!([{ "op": "move", "from": "/foo", "path": "/foo/bar/baz" }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "copy", "from": "/bar", "path": "/baz" }]), 0);
+        assert_rejected(json!({ "foo": 1 }), json!([{ "op": "test", "path": "/foo", "value": 1 }, { "op": "test", "path": "/foo", "value": 1.5 }]), 1);
+        assert_rejected(json!({ "foo": 1 }), json!({ "op": "add", "path": "/bar", "value": 2 }), 0); // not an array
+    }
+}
@@ -0,0 +1,85 @@
+use serde_json::{Map, Value};
+use crate::MAX_DEPTH;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// # Key Case
+///
+/// A naming convention for `FigCon::to_string_with_keys()` to rename object keys into
+pub enum KeyCase {
+    /// `maxThreads`
+    CamelCase,
+    /// `max_threads`
+    SnakeCase,
+    /// `max-threads`
+    KebabCase,
+    /// `MaxThreads`
+    PascalCase
+}
+
+impl KeyCase {
+    /// # Convert
+    ///
+    /// Rename one key, splitting it into words at `_`, `-`, spaces and lower-to-upper case changes (`HTTPServer` is `http` + `server`)
+    ///
+    /// Leading `_` and `-` are kept as they are, so reserved keys like `__version` keep their prefix
+    pub(crate) fn convert(&self, key: &str) -> String {
+        let body = key.trim_start_matches(['_', '-']);
+        let mut converted = key[..key.len() - body.len()].to_owned();
+        for (index, word) in words(body).iter().enumerate() {
+            match self {
+                KeyCase::SnakeCase | KeyCase::KebabCase => {
+                    if index > 0 { converted.push(if *self == KeyCase::SnakeCase { '_' } else { '-' }); }
+                    converted.push_str(&word.to_lowercase());
+                },
+                KeyCase::CamelCase if index == 0 => converted.push_str(&word.to_lowercase()),
+                KeyCase::CamelCase | KeyCase::PascalCase => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        converted.extend(first.to_uppercase());
+                        converted.push_str(&chars.as_str().to_lowercase());
+                    }
+                }
+            }
+        }
+        converted
+    }
+
+    /// # Rename Keys
+    ///
+    /// Copy a value with every object key renamed, including objects nested inside arrays
+    ///
+    /// Keys that end up with the same name collide, and the one that comes last wins.
+    /// `depth` is how many containers deep `value` sits, and anything from `MAX_DEPTH` down is copied as-is
+    pub(crate) fn rename_keys(&self, value: &Value, depth: usize) -> Value {
+        match value {
+            _ if depth >= MAX_DEPTH => value.clone(),
+            Value::Object(object) => Value::Object(object.iter()
+                .map(|(key, item)| (self.convert(key), self.rename_keys(item, depth + 1)))
+                .collect::<Map<String, Value>>()),
+            Value::Array(items) => Value::Array(items.iter().map(|item| self.rename_keys(item, depth + 1)).collect()),
+            other => other.clone()
+        }
+    }
+}
+
+/// # Words
+///
+/// Split a key into its words at separators and case changes
+fn words(key: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in key.split(['_', '-', ' ']).filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+        for window in 1..chars.len() {
+            let (prev, (at, current)) = (chars[window - 1].1, chars[window]);
+            let next_lower = chars.get(window + 1).is_some_and(|(_, next)| next.is_lowercase());
+            let boundary = current.is_uppercase() && (!prev.is_uppercase() || next_lower);
+            if boundary {
+                words.push(&part[start..at]);
+                start = at;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
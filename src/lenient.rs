@@ -0,0 +1,58 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// # FigCon Warning
+///
+/// Something a load had to work around, from `FigCon::load_warning()`
+pub enum FigConWarning {
+    /// The file wasn't valid JSON, but loaded after lenient repairs (trailing commas, single-quoted strings).
+    /// Saving the config writes it back in strict form
+    Repaired {
+        fixes: usize
+    }
+}
+
+/// # Repair
+///
+/// Rewrite common hand-editing mistakes into strict JSON: trailing commas before `}` or `]` are dropped
+/// and single-quoted strings become double-quoted. Nothing inside string literals is touched
+///
+/// Returns the repaired text and the number of fixes, or None if there was nothing to repair
+pub(crate) fn repair(text: &str) -> Option<(String, usize)> {
+    let mut out = String::with_capacity(text.len());
+    let mut fixes = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => {
+                out.push('"');
+                while let Some((_, c)) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => if let Some((_, escaped)) = chars.next() { out.push(escaped); },
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            },
+            '\'' => {
+                fixes += 1;
+                out.push('"');
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => match chars.next() {
+                            Some((_, '\'')) => out.push('\''),
+                            Some((_, escaped)) => { out.push('\\'); out.push(escaped); },
+                            None => {}
+                        },
+                        '"' => out.push_str("\\\""),
+                        '\'' => break,
+                        c => out.push(c)
+                    }
+                }
+                out.push('"');
+            },
+            ',' if text[index + 1..].trim_start().starts_with(['}', ']']) => fixes += 1,
+            c => out.push(c)
+        }
+    }
+    (fixes > 0).then_some((out, fixes))
+}
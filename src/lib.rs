@@ -1,14 +1,179 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    borrow::ToOwned, 
+    string::String, 
+    vec::Vec
+};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::{Map, Value, json, map::{Keys, Values}};
+#[cfg(feature = "std")]
+use audit::AuditLog;
+#[cfg(feature = "std")]
+use dirty::DirtyFlag;
+#[cfg(feature = "std")]
+use format::{ByteCounter, DepthLimited};
+#[cfg(feature = "std")]
 use std::{
-    fmt::Display, 
-    fs::File, 
+    borrow::Cow, 
+    cmp::Ordering, 
+    collections::BTreeMap, 
+    fmt::{
+        Debug, 
+        Display
+    }, 
+    hash::{
+        Hash, 
+        Hasher
+    }, 
+    fs::{
+        self, 
+        File, 
+        OpenOptions
+    }, 
     io::{
-        BufWriter, 
-        Read
+        self, 
+        ErrorKind, 
+        Read, 
+        Write
+    }, 
+    path::{
+        Path, 
+        PathBuf
+    }, 
+    sync::{
+        Arc, 
+        atomic::{
+            self, 
+            AtomicU64
+        }
     }, 
-    path::PathBuf
+    thread, 
+    time::{
+        Instant, 
+        SystemTime
+    }
 };
 
+#[cfg(feature = "std")]
+mod audit;
+#[cfg(feature = "std")]
+mod bound;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "log")]
+mod changelog;
+mod coerce;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod dirty;
+#[cfg(feature = "std")]
+mod env;
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+mod fields;
+#[cfg(feature = "std")]
+mod format;
+#[cfg(feature = "std")]
+mod group;
+#[cfg(feature = "std")]
+mod journal;
+#[cfg(feature = "std")]
+mod jsonpatch;
+#[cfg(feature = "std")]
+mod keycase;
+#[cfg(feature = "std")]
+mod lenient;
+mod merge;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "std")]
+mod namespace;
+#[cfg(feature = "std")]
+mod observer;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod query;
+#[cfg(feature = "std")]
+mod reload;
+#[cfg(feature = "std")]
+mod report;
+#[cfg(feature = "std")]
+mod root;
+#[cfg(feature = "std")]
+mod rotate;
+#[cfg(feature = "testing")]
+mod roundtrip;
+#[cfg(feature = "std")]
+mod schema;
+#[cfg(feature = "std")]
+mod secret;
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+mod storage;
+#[cfg(feature = "std")]
+mod table;
+#[cfg(feature = "std")]
+mod transaction;
+#[cfg(feature = "std")]
+mod typed;
+mod units;
+#[cfg(feature = "wasm")]
+mod web;
+
+#[cfg(feature = "std")]
+pub use bound::BoundFigCon;
+#[cfg(feature = "std")]
+pub use builder::FigConBuilder;
+#[cfg(feature = "std")]
+pub use diff::FigConChange;
+#[cfg(feature = "std")]
+pub use error::FigConError;
+#[cfg(feature = "std")]
+pub use format::{Codec, DisplayFormat, Format};
+#[cfg(feature = "std")]
+pub use group::FigConGroup;
+#[cfg(feature = "std")]
+pub use keycase::KeyCase;
+#[cfg(feature = "std")]
+pub use lenient::FigConWarning;
+pub use merge::ArrayMergeStrategy;
+#[cfg(feature = "std")]
+pub use namespace::NamespacedFigCon;
+#[cfg(feature = "std")]
+pub use observer::FigConObserver;
+#[cfg(feature = "keyring")]
+pub use secret::KeyringResolver;
+#[cfg(feature = "std")]
+pub use reload::ReloadOutcome;
+#[cfg(feature = "std")]
+pub use report::{FigConIssue, FigConReport, Severity};
+#[cfg(feature = "std")]
+pub use root::{FigConRoot, FigConRootKind};
+#[cfg(feature = "std")]
+pub use secret::{EnvResolver, SecretResolver};
+#[cfg(feature = "std")]
+pub use shared::SharedFigCon;
+#[cfg(feature = "std")]
+pub use stats::FigConStats;
+#[cfg(feature = "std")]
+pub use storage::{FileStorage, MemoryStorage, Storage};
+#[cfg(feature = "std")]
+pub use transaction::FigConTxn;
+#[cfg(feature = "wasm")]
+pub use web::WebStorage;
+
 pub trait ValueExtensions {
     fn obj(&self) -> Option<&Map<String, Value>>;
     fn obj_mut(&mut self) -> Option<&mut Map<String, Value>>;
@@ -23,6 +188,8 @@ pub trait ValueExtensions {
     fn get_key_mut_st(&mut self, key: &str) -> Option<&mut Value>;
     fn get_key(&self, key: String) -> Option<&Value>;
     fn get_key_st(&self, key: &str) -> Option<&Value>;
+    fn get_as<T: DeserializeOwned>(&self, key: String) -> Option<T>;
+    fn get_as_st<T: DeserializeOwned>(&self, key: &str) -> Option<T>;
     fn has_key(&self, key: String) -> bool;
     fn has_key_st(&self, key: &str) -> bool;
     fn remove_get_key(&mut self, key: String) -> Option<Value>;
@@ -37,6 +204,22 @@ pub trait ValueExtensions {
     fn get_obj_st(&self, key: &str) -> Option<&Value>;
     fn new_obj(&mut self, key: String) -> Option<&mut Value>;
     fn new_obj_st(&mut self, key: &str) -> Option<&mut Value>;
+    fn get_bytes(&self, key: String) -> Option<u64>;
+    fn get_bytes_st(&self, key: &str) -> Option<u64>;
+    fn get_duration(&self, key: String) -> Option<Duration>;
+    fn get_duration_st(&self, key: &str) -> Option<Duration>;
+    fn get_f64(&self, key: String) -> Option<f64>;
+    fn get_f64_st(&self, key: &str) -> Option<f64>;
+    fn get_i64(&self, key: String) -> Option<i64>;
+    fn get_i64_st(&self, key: &str) -> Option<i64>;
+    fn get_u64(&self, key: String) -> Option<u64>;
+    fn get_u64_st(&self, key: &str) -> Option<u64>;
+    fn get_bool(&self, key: String) -> Option<bool>;
+    fn get_bool_st(&self, key: &str) -> Option<bool>;
+    fn get_string(&self, key: String) -> Option<String>;
+    fn get_string_st(&self, key: &str) -> Option<String>;
+    fn merge(&mut self, overlay: Value, strategy: ArrayMergeStrategy);
+    fn patch(&mut self, patch: Value, strategy: ArrayMergeStrategy);
 }
 
 impl ValueExtensions for Value {
@@ -136,10 +319,19 @@ impl ValueExtensions for Value {
     /// 
     /// Will return None if used on non-objects
     fn get_key_mut(&mut self, key: String) -> Option<&mut Value> {
+        self.get_key_mut_st(&key)
+    }
+
+    /// # Get Key (Mutable, Static)
+    /// 
+    /// Acquire a key's value within an object within a value
+    /// 
+    /// Will return None if used on non-objects
+    fn get_key_mut_st(&mut self, key: &str) -> Option<&mut Value> {
         match self.obj_mut() {
             Some(object) => {
-                if object.contains_key(&key) {
-                    Some(&mut object[&key])
+                if object.contains_key(key) {
+                    Some(&mut object[key])
                 } else {
                     None
                 }
@@ -148,25 +340,25 @@ impl ValueExtensions for Value {
         }
     }
 
-    /// # Get Key (Mutable, Static)
+    /// # Get Key
     /// 
     /// Acquire a key's value within an object within a value
     /// 
     /// Will return None if used on non-objects
-    fn get_key_mut_st(&mut self, key: &str) -> Option<&mut Value> {
-        self.get_key_mut(key.to_owned())
+    fn get_key(&self, key: String) -> Option<&Value> {
+        self.get_key_st(&key)
     }
 
-    /// # Get Key
+    /// # Get Key (Static)
     /// 
     /// Acquire a key's value within an object within a value
     /// 
     /// Will return None if used on non-objects
-    fn get_key(&self, key: String) -> Option<&Value> {
+    fn get_key_st(&self, key: &str) -> Option<&Value> {
         match self.obj() {
             Some(object) => {
-                if object.contains_key(&key) {
-                    Some(&object[&key])
+                if object.contains_key(key) {
+                    Some(&object[key])
                 } else {
                     None
                 }
@@ -175,13 +367,22 @@ impl ValueExtensions for Value {
         }
     }
 
-    /// # Get Key (Static)
+    /// # Get Key As
     /// 
-    /// Acquire a key's value within an object within a value
+    /// Acquire a key's value within an object within a value, deserialized into `T`
     /// 
-    /// Will return None if used on non-objects
-    fn get_key_st(&self, key: &str) -> Option<&Value> {
-        self.get_key(key.to_owned())
+    /// Will return None if used on non-objects or if the value doesn't fit `T`
+    fn get_as<T: DeserializeOwned>(&self, key: String) -> Option<T> {
+        self.get_as_st(&key)
+    }
+
+    /// # Get Key As (Static)
+    /// 
+    /// Acquire a key's value within an object within a value, deserialized into `T`
+    /// 
+    /// Will return None if used on non-objects or if the value doesn't fit `T`
+    fn get_as_st<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        serde_json::from_value(self.get_key_st(key)?.clone()).ok()
     }
 
     /// # Has Key
@@ -190,12 +391,7 @@ impl ValueExtensions for Value {
     /// 
     /// Automatically returns false if used on non-objects
     fn has_key(&self, key: String) -> bool {
-        match self.obj() {
-            Some(object) => {
-                object.contains_key(&key)
-            },
-            None => false
-        }
+        self.has_key_st(&key)
     }
 
     /// # Has Key
@@ -204,7 +400,12 @@ impl ValueExtensions for Value {
     /// 
     /// Automatically returns false if used on non-objects
     fn has_key_st(&self, key: &str) -> bool {
-        self.has_key(key.to_owned())
+        match self.obj() {
+            Some(object) => {
+                object.contains_key(key)
+            },
+            None => false
+        }
     }
 
     /// # Remove and Get Key
@@ -213,12 +414,7 @@ impl ValueExtensions for Value {
     /// 
     /// Will return None if used on non-objects
     fn remove_get_key(&mut self, key: String) -> Option<Value> {
-        match self.obj_mut() {
-            Some(object) => {
-                object.remove(&key)
-            },
-            None => None
-        }
+        self.remove_get_key_st(&key)
     }
 
     /// # Remove and Get Key (Static)
@@ -227,7 +423,15 @@ impl ValueExtensions for Value {
     /// 
     /// Will return None if used on non-objects
     fn remove_get_key_st(&mut self, key: &str) -> Option<Value> {
-        self.remove_get_key(key.to_owned())
+        match self.obj_mut() {
+            Some(object) => {
+                #[cfg(feature = "preserve_order")]
+                return object.shift_remove(key); // keep the remaining keys in their original order
+                #[cfg(not(feature = "preserve_order"))]
+                object.remove(key)
+            },
+            None => None
+        }
     }
 
     /// # Remove Key
@@ -236,7 +440,7 @@ impl ValueExtensions for Value {
     /// 
     /// Will do nothing if used on non-objects
     fn remove_key(&mut self, key: String) {
-        self.remove_get_key(key);
+        self.remove_key_st(&key);
     }
 
     /// # Remove Key (Static)
@@ -245,7 +449,7 @@ impl ValueExtensions for Value {
     /// 
     /// Will do nothing if used on non-objects
     fn remove_key_st(&mut self, key: &str) {
-        self.remove_key(key.to_owned());
+        self.remove_get_key_st(key);
     }
 
     /// # Set Object
@@ -281,8 +485,17 @@ impl ValueExtensions for Value {
     /// 
     /// Will return None if used on non-objects
     fn get_obj_mut(&mut self, key: String) -> Option<&mut Value> {
+        self.get_obj_mut_st(&key)
+    }
+
+    /// # Get Object (Mutable, Static)
+    /// 
+    /// Get an object within the keys
+    /// 
+    /// Will return None if used on non-objects
+    fn get_obj_mut_st(&mut self, key: &str) -> Option<&mut Value> {
         if !self.is_object() { return None; }
-        match self.get_key_mut(key) {
+        match self.get_key_mut_st(key) {
             Some(value) => {
                 if value.is_object() {
                     Some(value)
@@ -292,23 +505,23 @@ impl ValueExtensions for Value {
         }
     }
 
-    /// # Get Object (Mutable, Static)
+    /// # Get Object
     /// 
     /// Get an object within the keys
     /// 
     /// Will return None if used on non-objects
-    fn get_obj_mut_st(&mut self, key: &str) -> Option<&mut Value> {
-        self.get_obj_mut(key.to_owned())
+    fn get_obj(&self, key: String) -> Option<&Value> {
+        self.get_obj_st(&key)
     }
 
-    /// # Get Object
+    /// # Get Object (Static)
     /// 
     /// Get an object within the keys
     /// 
     /// Will return None if used on non-objects
-    fn get_obj(&self, key: String) -> Option<&Value> {
+    fn get_obj_st(&self, key: &str) -> Option<&Value> {
         if !self.is_object() { return None; }
-        match self.get_key(key) {
+        match self.get_key_st(key) {
             Some(value) => {
                 if value.is_object() {
                     Some(value)
@@ -318,15 +531,6 @@ impl ValueExtensions for Value {
         }
     }
 
-    /// # Get Object (Static)
-    /// 
-    /// Get an object within the keys
-    /// 
-    /// Will return None if used on non-objects
-    fn get_obj_st(&self, key: &str) -> Option<&Value> {
-        self.get_obj(key.to_owned())
-    }
-
     /// # New Object
     /// 
     /// Create a child structure within the current config with a given key
@@ -346,281 +550,3999 @@ impl ValueExtensions for Value {
     fn new_obj_st(&mut self, key: &str) -> Option<&mut Value> {
         self.new_obj(key.to_owned())
     }
-}
-
-#[derive(Clone, Debug)]
-/// # FigCon
-/// 
-/// A simple synchronous config manager that relies on serde_json
-///
-/// It stores its own path location and can be saved/reloaded at any time
-pub struct FigCon {
-    live_config: Value,
-    location: PathBuf
-}
 
-impl Display for FigCon {
-    /// # Format
+    /// # Get Bytes
     /// 
-    /// A potentially slow function which attempts to display the entire configuration as a prettified json string
+    /// Acquire a key's value as a byte size, parsing strings like "10MB" or "512 KiB"
     /// 
-    /// It would be inadvisable to use this on larger configurations during runtime
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", serde_json::to_string_pretty(&self.live_config).unwrap())
-    }
-}
-
-impl FigCon {
-    /// # Initialize the FigCon
+    /// Plain numbers are treated as bytes. `KB`, `MB`, ... are decimal and `KiB`, `MiB`, ... binary (1KB = 1000 bytes, 1KiB = 1024 bytes)
     /// 
-    /// Attempts to load a config file with the given PathBuf
-    /// and returns an empty FigCon when it fails
-    pub fn load_or_default(path: PathBuf) -> Self {
-        if path.exists() && let Ok(file) = File::open(&path) {
-            let mut buffer: String = Default::default();
-            (&file).read_to_string(&mut buffer).expect("Failed to read config from storage");
-            let json: Value = serde_json::from_str(&buffer).expect("JSON deserialization failed");
-            FigCon { live_config: json, location: path }
-        } else {
-            FigCon { live_config: serde_json::Value::Object(Default::default()), location: path }
-        }
+    /// Will return None if used on non-objects or if the value can't be parsed
+    fn get_bytes(&self, key: String) -> Option<u64> {
+        self.get_bytes_st(&key)
     }
 
-    /// # Set Config Path
+    /// # Get Bytes (Static)
     /// 
-    /// Changing the location during runtime will not affect the live config, and it will not save to the new location automatically.
+    /// Acquire a key's value as a byte size, parsing strings like "10MB" or "512 KiB"
     /// 
-    /// Use `.save()` immediately after if you wish to write the live config to the new location
-    pub fn set_path(&mut self, path: PathBuf) {
-        self.location = path;
-    }
-
-    /// # Reload Config
+    /// Plain numbers are treated as bytes. `KB`, `MB`, ... are decimal and `KiB`, `MiB`, ... binary (1KB = 1000 bytes, 1KiB = 1024 bytes)
     /// 
-    /// Pull the config file again and overwrite the config in memory
-    pub fn reload(&mut self) -> Self {
-        Self::load_or_default(self.location.clone())
+    /// Will return None if used on non-objects or if the value can't be parsed
+    fn get_bytes_st(&self, key: &str) -> Option<u64> {
+        match self.get_key_st(key)? {
+            Value::Number(number) => number.as_u64(),
+            Value::String(string) => units::parse_bytes(string),
+            _ => None
+        }
     }
 
-    /// # Save Config
+    /// # Get Duration
     /// 
-    /// Write the current config state synchronously to the file system
-    pub fn save(&self) {
-        let file = File::create(&self.location).expect("Failed to create config file"); // this works regardless of if file exists or not
-        let file = BufWriter::new(file); // this makes it orders of magnitude faser
-        serde_json::to_writer_pretty(file, &self.live_config).expect("Config JSON serialization / writeout failed");
-    }
-
-    /// # Any Keys
+    /// Acquire a key's value as a duration, parsing strings like "30s", "250ms" or "2h"
     /// 
-    /// Returns true if the object contains any keys (Length > 0)
+    /// Plain numbers are treated as seconds. Accepted units are ns, us, ms, s, m/min, h and d
     /// 
-    /// Automatically returns false if used on non-objects
-    pub fn any_keys(&self) -> bool {
-        self.live_config.any_keys()
+    /// Will return None if used on non-objects or if the value can't be parsed
+    fn get_duration(&self, key: String) -> Option<Duration> {
+        self.get_duration_st(&key)
     }
 
-    /// # List Keys
+    /// # Get Duration (Static)
     /// 
-    /// Attempts to return all keys within an object
-    pub fn list_keys(&self) -> Option<Vec<String>> {
-        self.live_config.list_keys()
-    }
-
-    /// # List Values
+    /// Acquire a key's value as a duration, parsing strings like "30s", "250ms" or "2h"
     /// 
-    /// Attempts to return all values within an object
-    pub fn list_values(&self) -> Option<Vec<Value>> {
-        self.live_config.list_values()
-    }
-
-    /// # Iterate Keys
+    /// Plain numbers are treated as seconds. Accepted units are ns, us, ms, s, m/min, h and d
     /// 
-    /// Attempts to return an iterator for the keys within the current object.
-    pub fn iter_keys(&mut self) -> Option<Keys<'_>> {
-        self.live_config.iter_keys()
+    /// Will return None if used on non-objects or if the value can't be parsed
+    fn get_duration_st(&self, key: &str) -> Option<Duration> {
+        match self.get_key_st(key)? {
+            Value::Number(number) => Duration::try_from_secs_f64(number.as_f64()?).ok(),
+            Value::String(string) => units::parse_duration(string),
+            _ => None
+        }
     }
 
-    /// # Iterate Values
+    /// # Get F64
     /// 
-    /// Attempts to return an iterator for the values within the current object.
-    pub fn iter_values(&mut self) -> Option<Values<'_>> {
-        self.live_config.iter_values()
-    }
-
-    /// # Get Key (Mutable)
+    /// Acquire a key's value as an f64, coercing compatible values
     /// 
-    /// Acquire a key's value within an object within a value
+    /// Accepts any JSON number (integers included) or a string holding a finite number
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_key_mut(&mut self, key: String) -> Option<&mut Value> {
-        self.live_config.get_key_mut(key)
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_f64(&self, key: String) -> Option<f64> {
+        self.get_f64_st(&key)
     }
 
-    /// # Get Key (Mutable, Static)
+    /// # Get F64 (Static)
     /// 
-    /// Acquire a key's value within an object within a value
+    /// Acquire a key's value as an f64, coercing compatible values
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_key_mut_st(&mut self, key: &str) -> Option<&mut Value> {
-        self.get_key_mut(key.to_owned())
+    /// Accepts any JSON number (integers included) or a string holding a finite number
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_f64_st(&self, key: &str) -> Option<f64> {
+        coerce::to_f64(self.get_key_st(key)?)
     }
 
-    /// # Get Key
+    /// # Get I64
     /// 
-    /// Acquire a key's value within an object within a value
+    /// Acquire a key's value as an i64, coercing compatible values
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_key(&self, key: String) -> Option<&Value> {
-        self.live_config.get_key(key)
+    /// Accepts integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_i64(&self, key: String) -> Option<i64> {
+        self.get_i64_st(&key)
     }
 
-    /// # Get Key (Static)
+    /// # Get I64 (Static)
     /// 
-    /// Acquire a key's value within an object within a value
+    /// Acquire a key's value as an i64, coercing compatible values
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_key_st(&self, key: &str) -> Option<&Value> {
-        self.get_key(key.to_owned())
+    /// Accepts integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_i64_st(&self, key: &str) -> Option<i64> {
+        coerce::to_i64(self.get_key_st(key)?)
     }
 
-    /// # Set Key
+    /// # Get U64
     /// 
-    /// Assign a key's value within an object within a value
+    /// Acquire a key's value as a u64, coercing compatible values
     /// 
-    /// Will do nothing if used on non-objects
-    pub fn set_key(&mut self, key: String, value: Value) {
-        self.live_config.set_key(key, value);
+    /// Accepts non-negative integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_u64(&self, key: String) -> Option<u64> {
+        self.get_u64_st(&key)
     }
 
-    /// # Set Key (Static)
+    /// # Get U64 (Static)
     /// 
-    /// Assign a key's value within an object within a value
+    /// Acquire a key's value as a u64, coercing compatible values
     /// 
-    /// Will do nothing if used on non-objects
-    pub fn set_key_st(&mut self, key: &str, value: Value) {
-        self.set_key(key.to_owned(), value);
+    /// Accepts non-negative integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_u64_st(&self, key: &str) -> Option<u64> {
+        coerce::to_u64(self.get_key_st(key)?)
     }
 
-    /// # Has Key
+    /// # Get Bool
     /// 
-    /// Check if a key exists within an object within a value
+    /// Acquire a key's value as a bool, coercing compatible values
     /// 
-    /// Automatically returns false if used on non-objects
-    pub fn has_key(&self, key: String) -> bool {
-        self.live_config.has_key(key)
+    /// Accepts booleans, the numbers 0 and 1, or the strings true/false, yes/no, on/off and 1/0 (case-insensitive)
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_bool(&self, key: String) -> Option<bool> {
+        self.get_bool_st(&key)
     }
 
-    /// # Has Key
+    /// # Get Bool (Static)
     /// 
-    /// Check if a key exists within an object within a value
+    /// Acquire a key's value as a bool, coercing compatible values
     /// 
-    /// Automatically returns false if used on non-objects
-    pub fn has_key_st(&self, key: &str) -> bool {
-        self.has_key(key.to_owned())
+    /// Accepts booleans, the numbers 0 and 1, or the strings true/false, yes/no, on/off and 1/0 (case-insensitive)
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_bool_st(&self, key: &str) -> Option<bool> {
+        coerce::to_bool(self.get_key_st(key)?)
     }
 
-    /// # Remove and Get Key
+    /// # Get String
     /// 
-    /// Remove an object's key within a value and return it if it exists
+    /// Acquire a key's value as a String, coercing compatible values
     /// 
-    /// Will return None if used on non-objects
-    pub fn remove_get_key(&mut self, key: String) -> Option<Value> {
-        self.live_config.remove_get_key(key)
+    /// Accepts strings as-is, or numbers and booleans written out as text (`8080` becomes "8080")
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_string(&self, key: String) -> Option<String> {
+        self.get_string_st(&key)
     }
 
-    /// # Remove and Get Key (Static)
+    /// # Get String (Static)
     /// 
-    /// Remove an object's key within a value and return it if it exists
+    /// Acquire a key's value as a String, coercing compatible values
     /// 
-    /// Will return None if used on non-objects
-    pub fn remove_get_key_st(&mut self, key: &str) -> Option<Value> {
-        self.remove_get_key(key.to_owned())
+    /// Accepts strings as-is, or numbers and booleans written out as text (`8080` becomes "8080")
+    /// 
+    /// Will return None if used on non-objects or if the value can't be coerced
+    fn get_string_st(&self, key: &str) -> Option<String> {
+        coerce::to_string(self.get_key_st(key)?)
     }
 
-    /// # Remove Key
-    /// 
-    /// Remove an object's key within a value without respect to whether its assigned, or where its value goes
+    /// # Merge
     /// 
-    /// Will do nothing if used on non-objects
-    pub fn remove_key(&mut self, key: String) {
-        self.remove_get_key(key);
+    /// Deep-merge another value into this one: objects are combined key by key,
+    /// arrays are combined according to `strategy`, and everything else is overwritten
+    fn merge(&mut self, overlay: Value, strategy: ArrayMergeStrategy) {
+        merge::merge_into(self, overlay, strategy, false, 0);
     }
 
-    /// # Remove Key (Static)
-    /// 
-    /// Remove an object's key within a value without respect to whether its assigned, or where its value goes
+    /// # Patch
     /// 
-    /// Will do nothing if used on non-objects
-    pub fn remove_key_st(&mut self, key: &str) {
-        self.remove_key(key.to_owned());
+    /// Like `.merge()`, but a null in the patch removes that key instead of storing null (JSON Merge Patch, RFC 7396)
+    fn patch(&mut self, patch: Value, strategy: ArrayMergeStrategy) {
+        merge::merge_into(self, patch, strategy, true, 0);
     }
+}
 
-    /// # Set Object
-    /// 
-    /// Overwrite an object within the value, and combine the keys inside
-    /// 
-    /// Will do nothing if used on non-objects
-    pub fn set_obj(&mut self, key: String, object: Value) {
-        self.live_config.set_obj(key, object);
+#[cfg(feature = "std")]
+#[derive(Clone)]
+/// # FigCon
+/// 
+/// A simple synchronous config manager that relies on serde_json
+///
+/// It stores its own path location and can be saved/reloaded at any time
+pub struct FigCon {
+    live_config: Value,
+    location: PathBuf,
+    trailing_newline: bool,
+    skip_nulls: bool,
+    nofollow: bool,
+    fsync: bool,
+    strict_paths: bool,
+    path_separator: char,
+    feature_namespace: String,
+    skip_unchanged: bool,
+    rotation: Option<(u64, usize)>,
+    lenient: bool,
+    load_warning: Option<FigConWarning>,
+    mode: Option<u32>,
+    journal: Option<PathBuf>,
+    audit: Option<AuditLog>,
+    save_retries: (u32, Duration),
+    defaults: Value,
+    expires: BTreeMap<String, u64>,
+    loaded_hash: Option<u64>,
+    reload_interval: Duration,
+    last_reload: Option<Instant>,
+    secrets: Vec<String>,
+    secret_resolution: bool,
+    resolvers: Vec<Arc<dyn SecretResolver>>,
+    #[cfg(feature = "log")]
+    change_logging: bool,
+    format: Format,
+    display_format: DisplayFormat,
+    dirty: DirtyFlag,
+    last_mutated: Option<Instant>,
+    storage: Option<Arc<dyn Storage>>,
+    codec: Option<Arc<dyn Codec>>,
+    observer: Option<Arc<dyn FigConObserver>>
+}
+
+#[cfg(feature = "std")]
+impl Debug for FigCon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FigCon")
+            .field("live_config", &self.live_config)
+            .field("location", &self.location)
+            .field("storage", &self.storage.is_some())
+            .field("codec", &self.codec.is_some())
+            .finish_non_exhaustive()
     }
+}
 
-    /// # Set Object (Static)
+#[cfg(feature = "std")]
+impl Display for FigCon {
+    /// # Format
     /// 
-    /// Overwrite an object within the value, and combine the keys inside
+    /// A potentially slow function which attempts to display the entire configuration as a string,
+    /// prettified or compact as chosen with `.with_display_format()` (See `.to_pretty_string()` and `.to_compact_string()`)
     /// 
-    /// Will do nothing if used on non-objects
-    pub fn set_obj_st(&mut self, key: &str, object: Value) {
-        self.set_obj(key.to_owned(), object);
+    /// The alternate flag (`{:#}`) always prettifies. It would be inadvisable to use this on larger configurations during runtime
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = if f.alternate() || self.display_format == DisplayFormat::Pretty {
+            self.to_pretty_string()
+        } else {
+            self.to_compact_string()
+        };
+        f.write_str(&text.map_err(|_| std::fmt::Error)?)
     }
+}
 
-    /// # Get Object (Mutable)
+#[cfg(feature = "std")]
+impl PartialEq for FigCon {
+    /// # Equality
     /// 
-    /// Get an object within the keys
+    /// Two configs are equal when their live configs hold the same values
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_obj_mut(&mut self, key: String) -> Option<&mut Value> {
-        self.live_config.get_obj_mut(key)
+    /// The path is deliberately excluded, so the same config loaded from two locations compares equal
+    fn eq(&self, other: &Self) -> bool {
+        self.live_config == other.live_config
     }
+}
 
-    /// # Get Object (Mutable, Static)
-    /// 
-    /// Get an object within the keys
+#[cfg(feature = "std")]
+impl Eq for FigCon {}
+
+#[cfg(feature = "std")]
+impl Hash for FigCon {
+    /// # Hash
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_obj_mut_st(&mut self, key: &str) -> Option<&mut Value> {
-        self.get_obj_mut(key.to_owned())
+    /// Hash the live config consistently with equality (The path is excluded, and object key order doesn't matter)
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(&self.live_config, state);
     }
+}
 
-    /// # Get Object
-    /// 
-    /// Get an object within the keys
-    /// 
-    /// Will return None if used on non-objects
-    pub fn get_obj(&self, key: String) -> Option<&Value> {
-        self.live_config.get_obj(key)
+#[cfg(feature = "std")]
+/// # Hash Value
+/// 
+/// Recursively hash a value, visiting object keys in sorted order so equal objects always hash the same
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+    match value {
+        Value::Null => {},
+        Value::Bool(boolean) => boolean.hash(state),
+        Value::Number(number) => number.hash(state),
+        Value::String(string) => string.hash(state),
+        Value::Array(array) => {
+            array.len().hash(state);
+            array.iter().for_each(|item| hash_value(item, state));
+        },
+        Value::Object(object) => {
+            object.len().hash(state);
+            let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            for (key, item) in entries {
+                key.hash(state);
+                hash_value(item, state);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// # Without Nulls
+/// 
+/// Clone a value, recursively leaving out every object entry whose value is null (Nulls inside arrays are kept)
+/// 
+/// `depth` is how many containers deep `value` sits, and anything from `MAX_DEPTH` down is cloned as-is
+fn without_nulls(value: &Value, depth: usize) -> Value {
+    match value {
+        _ if depth >= MAX_DEPTH => value.clone(),
+        Value::Object(object) => Value::Object(object.iter()
+            .filter(|(_, item)| !item.is_null())
+            .map(|(key, item)| (key.clone(), without_nulls(item, depth + 1)))
+            .collect()),
+        Value::Array(items) => Value::Array(items.iter().map(|item| without_nulls(item, depth + 1)).collect()),
+        other => other.clone()
+    }
+}
+
+#[cfg(feature = "std")]
+/// # Shrink Value
+/// 
+/// Shrink every string and array in a value to fit, returning the bytes of capacity released
+/// 
+/// `depth` is how many containers deep `value` sits, and nothing from `MAX_DEPTH` down is shrunk
+fn shrink_value(value: &mut Value, depth: usize) -> usize {
+    match value {
+        _ if depth >= MAX_DEPTH => 0,
+        Value::String(text) => {
+            let spare = text.capacity() - text.len();
+            text.shrink_to_fit();
+            spare - (text.capacity() - text.len())
+        },
+        Value::Array(items) => {
+            let spare = (items.capacity() - items.len()) * core::mem::size_of::<Value>();
+            items.shrink_to_fit();
+            let freed = spare - (items.capacity() - items.len()) * core::mem::size_of::<Value>();
+            freed + items.iter_mut().map(|item| shrink_value(item, depth + 1)).sum::<usize>()
+        },
+        Value::Object(object) => object.values_mut().map(|item| shrink_value(item, depth + 1)).sum(),
+        _ => 0
+    }
+}
+
+/// # Max Depth
+/// 
+/// How many levels containers may nest in a config (A flat object is 1 level), the same cap serde_json puts on JSON it parses
+/// 
+/// Recursive operations like merging, flattening and diffing stop descending here, so they never get near the end of the stack
+const MAX_DEPTH: usize = 128;
+
+#[cfg(feature = "std")]
+/// # Check Replace
+/// 
+/// Fail if strict paths are on and `value` would replace the container at `key` (`existing` is what's there now)
+fn check_replace(strict_paths: bool, existing: Option<&Value>, value: &Value, key: &str) -> Result<(), FigConError> {
+    let is_container = |value: &Value| value.is_object() || value.is_array();
+    if strict_paths && existing.is_some_and(is_container) && !is_container(value) {
+        return Err(FigConError::WouldReplaceContainer { key: key.to_owned() });
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+/// # Check Depth
+/// 
+/// Fail with `FigConError::TooDeep` if a value placed `at` containers deep (0 for the root, 1 for a top-level key's value)
+/// would make the config nest past `MAX_DEPTH`
+fn check_depth(value: &Value, at: usize) -> Result<(), FigConError> {
+    if at > MAX_DEPTH || exceeds_depth(value, MAX_DEPTH - at) {
+        return Err(FigConError::TooDeep { limit: MAX_DEPTH });
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+/// # Exceeds Depth
+/// 
+/// Whether containers nest more than `limit` levels deep (A flat object is 1 level, like `FigConStats::max_depth`)
+/// 
+/// Walks with an explicit stack instead of recursing, so even absurdly deep values can be checked safely
+fn exceeds_depth(value: &Value, limit: usize) -> bool {
+    let mut pending = vec![(value, 0)];
+    while let Some((value, depth)) = pending.pop() {
+        match value {
+            Value::Array(items) if depth < limit => pending.extend(items.iter().map(|item| (item, depth + 1))),
+            Value::Object(object) if depth < limit => pending.extend(object.values().map(|item| (item, depth + 1))),
+            Value::Array(_) | Value::Object(_) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(feature = "std")]
+/// # Is Stdio
+/// 
+/// Whether a config path is `-`, the CLI convention for stdin and stdout
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+#[cfg(feature = "std")]
+/// # Staged
+/// 
+/// Where `FigCon::stage()` left a save
+pub(crate) enum Staged {
+    /// The bytes are already stored, so there's nothing to write
+    Unchanged,
+    /// Stdout or custom storage, written directly instead of through a temporary file
+    Direct,
+    /// Written to this temporary file, ready to be renamed into place
+    File(PathBuf)
+}
+
+#[cfg(feature = "std")]
+/// # Staging Counter
+/// 
+/// Numbers the temporary files this process stages saves in, so no two of them ever share a name
+static STAGING_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "std")]
+/// # Staging Path
+/// 
+/// A fresh name for the temporary file a config is staged in before being renamed into place: beside it,
+/// with the process id and a per-process counter appended (`config.json.1234.0.tmp`), so concurrent saves never share one
+fn staging_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(format!(".{}.{}.tmp", std::process::id(), STAGING_COUNTER.fetch_add(1, atomic::Ordering::Relaxed)));
+    path.with_file_name(name)
+}
+
+#[cfg(feature = "std")]
+/// # Extends Key
+/// 
+/// The top-level key naming the base config a file inherits from, followed by `FigCon::load_with_inheritance()`
+const EXTENDS_KEY: &str = "extends";
+
+#[cfg(feature = "std")]
+/// # Expires Key
+/// 
+/// The reserved top-level key holding `set_with_ttl` expiry times in the saved file, as unix milliseconds by key
+/// (In memory they live beside the config instead, so they never show up among its keys)
+const EXPIRES_KEY: &str = "__expires";
+
+#[cfg(feature = "std")]
+/// # Now Millis
+/// 
+/// The current unix time in milliseconds (Always 0 on wasm32, which has no clock, so nothing expires there)
+fn now_millis() -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(since) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        return since.as_millis().try_into().unwrap_or(u64::MAX);
+    }
+    0
+}
+
+#[cfg(feature = "std")]
+/// # Is Retryable
+/// 
+/// IO error kinds worth retrying a save for, since they are usually transient
+fn is_retryable(kind: ErrorKind) -> bool {
+    matches!(kind, ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::ResourceBusy)
+}
+
+#[cfg(feature = "std")]
+impl<K: Into<String>> FromIterator<(K, Value)> for FigCon {
+    /// # Collect Into FigCon
+    /// 
+    /// Build a config from top-level key/value pairs
+    /// 
+    /// The collected config has an empty path, so use `.set_path()` before saving it
+    /// 
+    /// ```
+    /// use figcon::FigCon;
+    /// use serde_json::json;
+    /// 
+    /// let conf: FigCon = vec![("a", json!(1)), ("b", json!("two"))].into_iter().collect();
+    /// assert_eq!(conf.get_key_st("a"), Some(&json!(1)));
+    /// assert_eq!(conf.get_key_st("b"), Some(&json!("two")));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (K, Value)>>(iter: I) -> Self {
+        let mut conf = FigCon::from_parts(Value::Object(Default::default()), PathBuf::new());
+        conf.extend(iter);
+        conf
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Into<String>> Extend<(K, Value)> for FigCon {
+    /// # Extend FigCon
+    /// 
+    /// Bulk-insert top-level key/value pairs, overwriting keys that already exist
+    fn extend<I: IntoIterator<Item = (K, Value)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.set_key(key.into(), value);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl FigCon {
+    /// # Max Depth
+    /// 
+    /// How many levels containers may nest in a config, 128 like serde_json's own cap (A flat object is 1 level)
+    /// 
+    /// Loading anything deeper, in any format or codec, fails with `FigConError::TooDeep`, and so do the `try_` setters
+    /// (`try_set_key`, `try_set_key_path`, `push`, `apply_json_patch`, `FigConTxn::try_set_key`, ...) for values that would end up deeper.
+    /// The other setters and merges panic instead. Keeping configs this shallow is what keeps
+    /// recursive operations like `merge`, `flatten` and `diff` from overflowing the stack
+    pub const MAX_DEPTH: usize = MAX_DEPTH;
+
+    /// # Reserved Keys
+    /// 
+    /// Top-level keys set aside for config metadata like schema versions, checksums, includes and expiry times,
+    /// which user settings shouldn't use: `__version`, `__checksum`, `__expires`, `$include` and `$schema`
+    pub const RESERVED_KEYS: &'static [&'static str] = &["__version", "__checksum", EXPIRES_KEY, "$include", "$schema"];
+
+    /// # From Parts
+    /// 
+    /// Build a FigCon around a value and a path with every option at its default
+    fn from_parts(live_config: Value, location: PathBuf) -> Self {
+        let mut conf = FigCon {
+            live_config,
+            location,
+            trailing_newline: true,
+            skip_nulls: false,
+            nofollow: false,
+            fsync: false,
+            strict_paths: false,
+            path_separator: '.',
+            feature_namespace: "features".to_owned(),
+            skip_unchanged: false,
+            rotation: None,
+            lenient: false,
+            load_warning: None,
+            mode: None,
+            journal: None,
+            audit: None,
+            save_retries: (0, Duration::ZERO),
+            defaults: Value::Object(Map::new()),
+            expires: BTreeMap::new(),
+            loaded_hash: None,
+            reload_interval: Duration::ZERO,
+            last_reload: None,
+            secrets: Vec::new(),
+            secret_resolution: false,
+            resolvers: vec![
+                Arc::new(EnvResolver),
+                #[cfg(feature = "keyring")]
+                Arc::new(KeyringResolver)
+            ],
+            #[cfg(feature = "log")]
+            change_logging: false,
+            format: Format::Json,
+            display_format: DisplayFormat::Pretty,
+            dirty: DirtyFlag::default(),
+            last_mutated: None,
+            storage: None,
+            codec: None,
+            observer: None
+        };
+        conf.take_expiry();
+        conf
+    }
+
+    /// # Take Expiry
+    /// 
+    /// Move the expiry times saved under `__expires` out of the live config, replacing the ones in memory
+    /// 
+    /// Does nothing if there is no `__expires` object, so replaying a journal without expiry records keeps them
+    fn take_expiry(&mut self) {
+        if !self.live_config.get_key_st(EXPIRES_KEY).is_some_and(Value::is_object) { return; }
+        let Some(Value::Object(expires)) = self.live_config.remove_get_key_st(EXPIRES_KEY) else { return; };
+        self.expires = expires.into_iter().filter_map(|(key, at)| Some((key, at.as_u64()?))).collect();
+    }
+
+    /// # Initialize the FigCon
+    /// 
+    /// Attempts to load a config file with the given PathBuf
+    /// and returns an empty FigCon when it fails
+    pub fn load_or_default(path: PathBuf) -> Self {
+        Self::load_with_format(path, Format::Json)
+    }
+
+    /// # Initialize the FigCon With a Format
+    /// 
+    /// Attempts to load a config file stored in the given format and returns an empty FigCon when it fails
+    /// 
+    /// The FigCon keeps the format, so `.save()` and `.reload()` use it too
+    /// 
+    /// Following the CLI convention, the path `-` reads the config from stdin (an empty stdin gives an empty FigCon),
+    /// and `.save()` then writes it to stdout. Stdin can only be read once, so `.reload()` keeps the config as it is
+    pub fn load_with_format(path: PathBuf, format: Format) -> Self {
+        let mut conf = if is_stdio(&path) {
+            let mut buffer: Vec<u8> = Default::default();
+            std::io::stdin().lock().read_to_end(&mut buffer).expect("Failed to read config from stdin");
+            let value = if buffer.is_empty() { Value::Object(Map::new()) } else { format.decode(&buffer).expect("Config deserialization failed") };
+            FigCon::from_parts(value, path)
+        } else if path.exists() && let Ok(file) = File::open(&path) {
+            let mut buffer: Vec<u8> = Default::default();
+            (&file).read_to_end(&mut buffer).expect("Failed to read config from storage");
+            let value = format.decode(&buffer).expect("Config deserialization failed");
+            let mut conf = FigCon::from_parts(value, path);
+            conf.loaded_hash = Some(Self::content_hash(&buffer));
+            conf
+        } else {
+            FigCon::from_parts(serde_json::Value::Object(Default::default()), path)
+        };
+        conf.format = format;
+        conf
+    }
+
+    /// # Builder
+    /// 
+    /// Start a `FigConBuilder`, which layers a user's override file over defaults embedded in the program
+    pub fn builder() -> FigConBuilder {
+        FigConBuilder::default()
+    }
+
+    /// # Load With Report
+    /// 
+    /// Run a builder's whole load pipeline (defaults, override file, env overlay, validation) without stopping at the first problem,
+    /// returning the config along with a report of every issue found, graded by severity
+    /// 
+    /// Broken parts are skipped with an `Error` in the report: malformed defaults start from an empty config,
+    /// and a malformed override file leaves just the defaults. Whether the result is usable is up to the caller
+    pub fn load_with_report(builder: FigConBuilder) -> (FigCon, FigConReport) {
+        builder.build_with_report()
+    }
+
+    /// # Initialize the FigCon With Storage
+    /// 
+    /// Load a config from a custom storage backend in the given format, and save back to it
+    /// 
+    /// Missing data (`NotFound`) gives an empty FigCon. Any other read or parse failure panics like `load_or_default`.
+    /// The path is unused in this mode, so path-based options like `with_nofollow` have no effect
+    pub fn with_storage(storage: Box<dyn Storage>, format: Format) -> Self {
+        let (value, hash) = Self::read_storage(storage.as_ref(), &format).expect("Failed to load config from storage");
+        let mut conf = FigCon::from_parts(value, PathBuf::new());
+        conf.format = format;
+        conf.loaded_hash = hash;
+        conf.storage = Some(Arc::from(storage));
+        conf
+    }
+
+    /// # Read Storage
+    /// 
+    /// Fetch and decode a storage backend's bytes, treating missing data as an empty config,
+    /// along with the hash of the bytes (None when missing)
+    fn read_storage(storage: &dyn Storage, codec: &dyn Codec) -> Result<(Value, Option<u64>), FigConError> {
+        match storage.read() {
+            Ok(bytes) => Ok((codec.decode(&bytes)?, Some(Self::content_hash(&bytes)))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok((Value::Object(Map::new()), None)),
+            Err(err) => Err(err.into())
+        }
+    }
+
+    /// # Load With Codec
+    /// 
+    /// Load a config file stored in a custom encoding, keeping the codec for `.save()` and `.reload()` (See `.with_codec()`)
+    /// 
+    /// A missing file gives an empty FigCon. Read and decode failures are returned
+    pub fn load_with_codec(path: PathBuf, codec: Box<dyn Codec>) -> Result<Self, FigConError> {
+        let mut conf = FigCon::from_parts(Value::Object(Map::new()), path).with_codec(codec);
+        if conf.location.exists() {
+            let bytes = fs::read(&conf.location)?;
+            conf.live_config = conf.codec().decode(&bytes)?;
+            conf.loaded_hash = Some(Self::content_hash(&bytes));
+            conf.take_expiry();
+        }
+        Ok(conf)
+    }
+
+    /// # Try Load
+    /// 
+    /// Attempts to load a config file with the given PathBuf, returning an error instead of panicking
+    /// 
+    /// The format is picked by file extension like `load_auto` (JSON if unknown), but without falling back to other formats.
+    /// A missing file is not an error and gives an empty FigCon, like `load_or_default`.
+    /// JSON parse errors quote the offending line with a caret under the column. For custom encodings, use `load_with_codec`
+    /// (TOML and YAML aren't built in, so `.toml`, `.yaml` and `.yml` files fail with `FigConError::UnsupportedFormat`)
+    pub fn try_load(path: PathBuf) -> Result<Self, FigConError> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        extension.map_or(Ok(()), Format::check_extension)?;
+        let format = extension.and_then(Format::from_extension).unwrap_or_default();
+        Self::try_load_with_format(path, format)
+    }
+
+    /// # Try Load With Format
+    /// 
+    /// Attempts to load a config file stored in the given format, returning an error instead of panicking like `load_with_format`
+    /// 
+    /// The FigCon keeps the format, so `.save()` and `.reload()` use it too. A missing file gives an empty FigCon
+    pub fn try_load_with_format(path: PathBuf, format: Format) -> Result<Self, FigConError> {
+        let bytes = if path.exists() { Some(fs::read(&path)?) } else { None };
+        let value = match &bytes {
+            Some(bytes) => format.decode(bytes)?,
+            None => Value::Object(Map::new())
+        };
+        let mut conf = FigCon::from_parts(value, path);
+        conf.format = format;
+        conf.loaded_hash = bytes.as_deref().map(Self::content_hash);
+        Ok(conf)
+    }
+
+    /// # Load Memory-Mapped
+    /// 
+    /// Load a config like `try_load`, but parse straight out of a read-only memory mapping of the file
+    /// instead of reading it into a buffer first (Needs the `mmap` feature, and maps only on unix; other platforms read normally)
+    /// 
+    /// This saves a full copy of very large files. The whole file is still parsed up front, and the mapping is released
+    /// as soon as parsing finishes, so the returned config never touches it again
+    /// 
+    /// Memory mapping is only safe while nothing else modifies the file: truncating or rewriting it during the load is undefined behavior
+    /// (on most platforms, a crash with `SIGBUS`). Don't use this on files other processes may be saving at the same time
+    #[cfg(feature = "mmap")]
+    pub fn load_mmap(path: PathBuf) -> Result<Self, FigConError> {
+        if !path.exists() {
+            return Ok(FigCon::from_parts(Value::Object(Map::new()), path));
+        }
+        let mapping = mmap::Mapping::open(&path)?;
+        let value = Format::Json.decode(&mapping)?;
+        let hash = Self::content_hash(&mapping);
+        drop(mapping);
+        let mut conf = FigCon::from_parts(value, path);
+        conf.loaded_hash = Some(hash);
+        Ok(conf)
+    }
+
+    /// # Load Auto
+    /// 
+    /// Load a config without dictating its format: the parser is picked by file extension
+    /// (`.json`, and with the `binary` feature `.cbor`, `.msgpack` or `.mpk`), and if the extension is unknown or that parse fails,
+    /// every other enabled format is tried in turn until one succeeds
+    /// 
+    /// A fallback format only counts if it parses to an object or array, since stray bytes often decode as a lone binary scalar.
+    /// The FigCon keeps the format that worked, so `.save()` writes it back the same way. A missing file gives an empty FigCon
+    /// in the extension's format (JSON if unknown). If no format parses, `FigConError::NoFormatMatched` lists what was tried
+    /// 
+    /// TOML and YAML aren't built in: `.toml`, `.yaml` and `.yml` files fail with `FigConError::UnsupportedFormat`
+    /// instead of being guessed at (Read those with `load_with_codec`)
+    pub fn load_auto(path: PathBuf) -> Result<Self, FigConError> {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        extension.map_or(Ok(()), Format::check_extension)?;
+        let guess = extension.and_then(Format::from_extension);
+        if !path.exists() {
+            let mut conf = FigCon::from_parts(Value::Object(Map::new()), path);
+            conf.format = guess.unwrap_or_default();
+            return Ok(conf);
+        }
+        let bytes = fs::read(&path)?;
+        let mut tried = Vec::new();
+        for format in guess.into_iter().chain(Format::ENABLED.iter().copied().filter(|format| Some(*format) != guess)) {
+            match format.decode(&bytes) {
+                // Most bytes decode as some lone binary scalar, so a fallback only counts if it finds a container
+                Ok(value) if Some(format) == guess || value.is_object() || value.is_array() => {
+                    let mut conf = FigCon::from_parts(value, path);
+                    conf.format = format;
+                    conf.loaded_hash = Some(Self::content_hash(&bytes));
+                    return Ok(conf);
+                },
+                _ => tried.push(format)
+            }
+        }
+        Err(FigConError::NoFormatMatched { tried })
+    }
+
+    /// # Load Directory
+    /// 
+    /// Load a drop-in config directory (like `/etc/myapp/conf.d`), deep-merging every `.json` file in it into one config
+    /// 
+    /// Files are merged in lexicographic filename order, so later files override earlier ones (`10-base.json` before `20-local.json`).
+    /// Arrays are replaced, not combined. Non-JSON files and subdirectories are skipped.
+    /// A fragment that fails to read or parse fails the load with `FigConError::Fragment` naming the file.
+    /// The config is bound to the directory itself, so use `.set_path()` before saving
+    pub fn load_dir(dir: &Path) -> Result<Self, FigConError> {
+        let mut fragments: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "json"))
+            .collect();
+        fragments.sort_by(|left, right| left.file_name().cmp(&right.file_name()));
+        let mut value = Value::Object(Map::new());
+        for fragment in fragments {
+            let parsed = fs::read(&fragment).map_err(FigConError::from).and_then(|bytes| Format::Json.decode(&bytes));
+            match parsed {
+                Ok(parsed) => merge::merge_into(&mut value, parsed, ArrayMergeStrategy::Replace, false, 0),
+                Err(err) => return Err(FigConError::Fragment { path: fragment, error: Box::new(err) })
+            }
+        }
+        Ok(FigCon::from_parts(value, dir.to_path_buf()))
+    }
+
+    /// # Load With Inheritance
+    /// 
+    /// Load a config that may declare `"extends": "base.json"`, meaning "start from the base, then apply my settings"
+    /// 
+    /// The base is loaded first (following its own `extends`, and so on), and each file is deep-merged over the one it extends,
+    /// so the file that was asked for wins over its base, which wins over its base. Arrays are replaced, not combined.
+    /// Relative `extends` paths resolve against the directory of the file declaring them. Only string values are followed
+    /// 
+    /// The `extends` keys are stripped from the result. A missing top file gives an empty FigCon like `try_load`,
+    /// but a missing or malformed base fails with `FigConError::Fragment` naming it, and a chain that loops back
+    /// fails with `FigConError::ExtendsCycle`
+    pub fn load_with_inheritance(path: PathBuf) -> Result<Self, FigConError> {
+        if !path.exists() {
+            return Ok(FigCon::from_parts(Value::Object(Map::new()), path));
+        }
+        let value = Self::read_extending(&path, &mut Vec::new())?;
+        Ok(FigCon::from_parts(value, path))
+    }
+
+    /// # Read Extending
+    /// 
+    /// Read a file and everything it extends, merged together, with `chain` holding the files already being loaded
+    fn read_extending(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Value, FigConError> {
+        let identity = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if chain.contains(&identity) {
+            return Err(FigConError::ExtendsCycle { path: path.to_path_buf() });
+        }
+        chain.push(identity);
+        let mut value = Format::Json.decode(&fs::read(path)?)?;
+        let Some(base) = value.get_key_st(EXTENDS_KEY).and_then(Value::as_str).map(PathBuf::from) else {
+            return Ok(value);
+        };
+        value.remove_key_st(EXTENDS_KEY);
+        let base = path.parent().unwrap_or(Path::new("")).join(base);
+        let mut merged = Self::read_extending(&base, chain).map_err(|err| match err {
+            err @ (FigConError::Fragment { .. } | FigConError::ExtendsCycle { .. }) => err,
+            err => FigConError::Fragment { path: base.clone(), error: Box::new(err) }
+        })?;
+        merge::merge_into(&mut merged, value, ArrayMergeStrategy::Replace, false, 0);
+        Ok(merged)
+    }
+
+    /// # Load Checked
+    /// 
+    /// Load a config like `try_load`, but first make sure the path can actually name a config file,
+    /// so a misconfigured path fails right away instead of `.save()` writing somewhere surprising later
+    /// 
+    /// The rules, in order: the path must not be empty (`FigConError::InvalidPath`), must not end in a separator
+    /// or in `..` (`InvalidPath`), must not be an existing directory (`PathIsDirectory`),
+    /// and its nearest existing ancestor must be a directory (`ParentNotDirectory`). Relative paths are fine
+    /// and resolve against the current directory, as everywhere else
+    pub fn load_checked(path: PathBuf) -> Result<Self, FigConError> {
+        let invalid = |reason: &str| Err(FigConError::InvalidPath { path: path.clone(), reason: reason.to_owned() });
+        if path.as_os_str().is_empty() {
+            return invalid("the path is empty");
+        }
+        if path.as_os_str().to_string_lossy().ends_with(std::path::is_separator) {
+            return invalid("it ends in a separator, so it names a directory");
+        }
+        if path.file_name().is_none() {
+            return invalid("it has no file name");
+        }
+        if path.is_dir() {
+            return Err(FigConError::PathIsDirectory { path });
+        }
+        if let Some(ancestor) = path.ancestors().skip(1).find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+            && !ancestor.is_dir() {
+            return Err(FigConError::ParentNotDirectory { path: ancestor.to_path_buf() });
+        }
+        Self::try_load(path)
+    }
+
+    /// # Try Load Lenient
+    /// 
+    /// Like `try_load`, but a file that isn't quite valid JSON gets lenient repairs before giving up:
+    /// trailing commas are dropped and single-quoted strings become double-quoted (never inside string literals)
+    /// 
+    /// A repaired load sets `.load_warning()` to `FigConWarning::Repaired`, so the app can offer to re-save in strict form.
+    /// The returned config keeps lenient parsing on for `.reload()`
+    pub fn try_load_lenient(path: PathBuf) -> Result<Self, FigConError> {
+        let (value, warning, hash) = Self::read_lenient(&path, Format::Json)?;
+        let mut conf = FigCon::from_parts(value, path).with_lenient_parsing(true);
+        conf.load_warning = warning;
+        conf.loaded_hash = hash;
+        Ok(conf)
+    }
+
+    /// # Read Lenient
+    /// 
+    /// Read and parse a file, retrying a failed JSON parse with lenient repairs, along with the hash of the bytes read
+    /// (None when the file is missing)
+    fn read_lenient(path: &Path, format: Format) -> Result<(Value, Option<FigConWarning>, Option<u64>), FigConError> {
+        if !path.exists() {
+            return Ok((Value::Object(Map::new()), None, None));
+        }
+        let bytes = fs::read(path)?;
+        let hash = Some(Self::content_hash(&bytes));
+        let err = match format.decode(&bytes) {
+            Ok(value) => return Ok((value, None, hash)),
+            Err(err @ FigConError::Parse { .. }) if format == Format::Json => err,
+            Err(err) => return Err(err)
+        };
+        let Some((text, fixes)) = std::str::from_utf8(&bytes).ok().and_then(|text| lenient::repair(text.trim_start_matches('\u{feff}'))) else {
+            return Err(err);
+        };
+        match format.decode(text.as_bytes()) {
+            Ok(value) => Ok((value, Some(FigConWarning::Repaired { fixes }), hash)),
+            Err(_) => Err(err) // the original error points at the real mistake
+        }
+    }
+
+    /// # Load Typed
+    /// 
+    /// Load a config file like `try_load` and deserialize it into `T` right away, returning both:
+    /// the FigCon for dynamic edits and saving, and the struct for typed reads
+    /// 
+    /// A config that doesn't fit `T` fails with `FigConError::Typed`, naming the dot-path of the offending value
+    /// (or of the object missing a field) along with serde's message
+    pub fn load_typed<T: DeserializeOwned>(path: PathBuf) -> Result<(Self, T), FigConError> {
+        let conf = Self::try_load(path)?;
+        let typed = typed::deserialize_traced(&conf.live_config).map_err(|(path, error)| FigConError::Typed { path, error })?;
+        Ok((conf, typed))
+    }
+
+    /// # Load With Max Depth
+    /// 
+    /// Like `try_load`, but rejects a config whose containers nest more than `depth` levels deep with `FigConError::TooDeep`
+    /// (For configs from untrusted sources, a flat object is 1 level)
+    /// 
+    /// Every load is already capped at `FigCon::MAX_DEPTH` (128) levels, whatever the format,
+    /// so this only needs to be used to enforce a stricter limit
+    pub fn load_with_max_depth(path: PathBuf, depth: usize) -> Result<Self, FigConError> {
+        let conf = Self::try_load(path)?;
+        if exceeds_depth(&conf.live_config, depth) {
+            return Err(FigConError::TooDeep { limit: depth });
+        }
+        Ok(conf)
+    }
+
+    /// # Load First Existing
+    /// 
+    /// Try each candidate path in order (CWD, home, /etc, ...) and load the first one that exists like `try_load`
+    /// 
+    /// When none exist, returns an empty FigCon bound to the last path so `.save()` creates it there.
+    /// A read or parse error in an existing file stops the search and is returned,
+    /// rather than silently falling through to a lower priority file. Use `.path()` to see which path was picked
+    pub fn load_first_existing(paths: &[PathBuf]) -> Result<Self, FigConError> {
+        match paths.iter().find(|path| path.exists()).or(paths.last()) {
+            Some(path) => Self::try_load(path.clone()),
+            None => Self::try_load(PathBuf::new())
+        }
+    }
+
+    /// # Config Path
+    /// 
+    /// The location this config loads from and saves to
+    pub fn path(&self) -> &Path {
+        &self.location
+    }
+
+    /// # Set Config Path
+    /// 
+    /// Changing the location during runtime will not affect the live config, and it will not save to the new location automatically.
+    /// 
+    /// Use `.save()` immediately after if you wish to write the live config to the new location (`-` meaning stdout)
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.location = path;
+    }
+
+    /// # Reload Config
+    /// 
+    /// Pull the config file again and overwrite the config in memory
+    /// 
+    /// A config read from stdin (the path `-`) can't be pulled again and is returned unchanged.
+    /// Audit lines still waiting for a save move over to the returned config
+    pub fn reload(&mut self) -> Self {
+        let mut reloaded = self.clone(); // keep every option, only the values are pulled again
+        reloaded.audit = self.audit.as_ref().map(AuditLog::hand_off);
+        if self.storage.is_none() && is_stdio(&self.location) {
+            return reloaded; // stdin was used up by the first load
+        }
+        reloaded.dirty.clear();
+        reloaded.load_warning = None;
+        reloaded.expires.clear(); // whatever the reloaded file holds replaces them
+        reloaded.live_config = match &self.storage {
+            Some(storage) => Self::read_storage(storage.as_ref(), self.codec()).expect("Failed to load config from storage").0,
+            None if self.codec.is_some() => self.read_saved().expect("Config deserialization failed"),
+            None if self.lenient => {
+                let (value, warning, _) = Self::read_lenient(&self.location, self.format).expect("Config deserialization failed");
+                reloaded.load_warning = warning;
+                value
+            },
+            None => {
+                let loaded = Self::load_with_format(self.location.clone(), self.format);
+                reloaded.expires = loaded.expires; // already taken out of the loaded value
+                loaded.live_config
+            }
+        };
+        if let Some(journal) = &self.journal {
+            journal::replay(journal, &mut reloaded.live_config).expect("Failed to replay config journal");
+        }
+        reloaded.take_expiry();
+        if let Some(observer) = &self.observer && (self.storage.is_some() || self.location.exists()) {
+            observer.on_load(&self.location);
+        }
+        reloaded
+    }
+
+    /// # Reload or Keep
+    /// 
+    /// Reload the config in place like `.reload()`, unless the file has gone missing or hasn't changed
+    /// 
+    /// A missing file keeps the config in memory untouched and returns `ReloadOutcome::FileMissing`,
+    /// instead of reloading to an empty config. This protects live settings when an admin deletes the file,
+    /// or while another process briefly removes it to replace it. The next `.save()` recreates it
+    /// 
+    /// A file whose bytes hash the same as the ones last loaded (by any loader reading a single file or storage backend,
+    /// or an earlier `.reload_or_keep()`) isn't parsed again and returns `ReloadOutcome::Unchanged`, so editors touching the file without changing it cost nothing.
+    /// Edits made in memory since are kept then, where a reload would have dropped them
+    /// 
+    /// With `.with_reload_interval()`, calls coming sooner than the interval after the last reload return `ReloadOutcome::Throttled`
+    /// without touching the file, so a file rewritten in a loop is reloaded at most once per interval, at its latest contents
+    pub fn reload_or_keep(&mut self) -> ReloadOutcome {
+        if self.last_reload.is_some_and(|last| last.elapsed() < self.reload_interval) {
+            return ReloadOutcome::Throttled;
+        }
+        let bytes = match &self.storage {
+            Some(storage) => storage.read(),
+            None => fs::read(&self.location)
+        };
+        let hash = match bytes {
+            Ok(bytes) => Some(Self::content_hash(&bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => return ReloadOutcome::FileMissing,
+            Err(_) => None // left for the reload to report
+        };
+        if hash.is_some() && hash == self.loaded_hash {
+            return ReloadOutcome::Unchanged;
+        }
+        *self = self.reload();
+        self.loaded_hash = hash;
+        self.last_reload = Some(Instant::now());
+        ReloadOutcome::Reloaded
+    }
+
+    /// # Content Hash
+    /// 
+    /// Hash the raw bytes of a config file, to tell whether it changed since it was loaded
+    fn content_hash(bytes: &[u8]) -> u64 {
+        let mut hasher = std::hash::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// # Save Config
+    /// 
+    /// Write the current config state synchronously to the file system
+    /// 
+    /// The file is replaced atomically: the config is written to a new temporary file beside it (`config.json.<pid>.<n>.tmp`)
+    /// and renamed over it, so a crash or a full disk never leaves a half-written config behind.
+    /// A symlinked config has its target replaced, and the link stays in place
+    /// 
+    /// A config whose path is `-` is written to stdout instead, directly and without any file handling
+    pub fn save(&self) {
+        if let Err(err) = self.try_save() {
+            panic!("Failed to save config: {}", err);
+        }
+    }
+
+    /// # Try Save Config
+    /// 
+    /// Write the current config state synchronously to the file system, returning an error instead of panicking
+    pub fn try_save(&self) -> Result<(), FigConError> {
+        self.finish_save(self.write_out_retrying())
+    }
+
+    /// # Finish Save
+    /// 
+    /// Settle the journal, audit log and dirty flag after a write, and tell the observer how it went
+    fn finish_save(&self, written: Result<usize, FigConError>) -> Result<(), FigConError> {
+        let result = written.and_then(|bytes| {
+            if let Some(journal) = &self.journal {
+                journal::truncate(journal)?; // everything journaled is in the main file now
+            }
+            if let Some(audit) = &self.audit {
+                audit.flush()?;
+            }
+            self.dirty.clear();
+            Ok(bytes)
+        });
+        if let Some(observer) = &self.observer {
+            match &result {
+                Ok(bytes) => observer.on_save(&self.location, *bytes),
+                Err(err) => observer.on_error(err)
+            }
+        }
+        result.map(|_| ())
+    }
+
+    /// # To Pretty String
+    /// 
+    /// Serialize the live config as pretty-printed text in its format, returning an error instead of panicking like `Display` would
+    /// 
+    /// Binary formats have no text form and render as JSON
+    pub fn to_pretty_string(&self) -> Result<String, FigConError> {
+        self.format.encode_text(&self.live_config, true)
+    }
+
+    /// # To Compact String
+    /// 
+    /// Serialize the live config as compact single-line text in its format, returning an error instead of panicking like `Display` would
+    /// 
+    /// Binary formats have no text form and render as JSON
+    pub fn to_compact_string(&self) -> Result<String, FigConError> {
+        self.format.encode_text(&self.live_config, false)
+    }
+
+    /// # To String With Keys
+    /// 
+    /// Serialize the live config like `.to_compact_string()`, with every object key renamed into `case` (`max_threads` becomes `maxThreads`)
+    /// 
+    /// This only affects the output and never the config itself. It is a one-way export:
+    /// converting back can't restore the original casing of keys like `HTTPPort`, and keys that convert to the same name collide
+    pub fn to_string_with_keys(&self, case: KeyCase) -> Result<String, FigConError> {
+        self.format.encode_text(&case.rename_keys(&self.live_config, 0), false)
+    }
+
+    /// # To Bytes
+    /// 
+    /// Serialize the live config exactly as it will be written to the file system
+    fn to_bytes(&self) -> Result<Vec<u8>, FigConError> {
+        let mut bytes = self.codec().encode(&self.saved_value())?;
+        if self.writes_newline() {
+            bytes.push(b'\n');
+        }
+        Ok(bytes)
+    }
+
+    /// # Writes Newline
+    /// 
+    /// Whether a newline is appended to the serialized config (Only JSON, and not through a custom codec)
+    fn writes_newline(&self) -> bool {
+        self.trailing_newline && self.codec.is_none() && self.format == Format::Json
+    }
+
+    /// # Codec
+    /// 
+    /// The custom codec if one is set, or else the format
+    fn codec(&self) -> &dyn Codec {
+        self.codec.as_deref().unwrap_or(&self.format)
+    }
+
+    /// # Serialized Length
+    /// 
+    /// The number of bytes `.save()` would write right now, counted while serializing instead of building the output
+    /// 
+    /// This still serializes the whole config, so it costs about as much as a save without the IO or the allocation.
+    /// A custom codec can only hand back whole buffers, so with one the output is built after all
+    pub fn serialized_len(&self) -> Result<usize, FigConError> {
+        if self.codec.is_some() {
+            return Ok(self.to_bytes()?.len());
+        }
+        let mut counter = ByteCounter::default();
+        self.format.encode_into(&self.saved_value(), &mut counter)?;
+        Ok(counter.0 + usize::from(self.writes_newline()))
+    }
+
+    /// # Saved Value
+    /// 
+    /// The value `.save()` writes: the live config itself, or a copy without its nulls (`.with_skip_nulls()`),
+    /// without any expired keys, and with the remaining expiry times under `__expires`
+    fn saved_value(&self) -> Cow<'_, Value> {
+        if self.expires.is_empty() && !self.skip_nulls {
+            return Cow::Borrowed(&self.live_config);
+        }
+        // filtered copy, the live config keeps its nulls and expired keys
+        let mut saved = if self.skip_nulls { without_nulls(&self.live_config, 0) } else { self.live_config.clone() };
+        if let Value::Object(object) = &mut saved && !self.expires.is_empty() {
+            let now = now_millis();
+            let mut expires = Map::new();
+            for (key, at) in &self.expires {
+                if *at <= now { object.remove(key); } else { expires.insert(key.clone(), json!(at)); }
+            }
+            if !expires.is_empty() {
+                object.insert(EXPIRES_KEY.to_owned(), Value::Object(expires));
+            }
+        }
+        Cow::Owned(saved)
+    }
+
+    /// # Write Out
+    /// 
+    /// Serialize and write the live config, returning how many bytes were written (0 for a skipped unchanged write)
+    fn write_out(&self) -> Result<usize, FigConError> {
+        let bytes = self.to_bytes()?; // serializing up front means a single write call
+        match self.stage(&bytes)? {
+            Staged::Unchanged => Ok(0),
+            Staged::Direct => match &self.storage {
+                Some(storage) => {
+                    storage.write(&bytes).map_err(|err| match err.kind() {
+                        ErrorKind::StorageFull => FigConError::QuotaExceeded,
+                        _ => FigConError::Io(err)
+                    })?;
+                    Ok(bytes.len())
+                },
+                None => {
+                    let mut stdout = std::io::stdout().lock(); // not seekable, so always a plain write
+                    stdout.write_all(&bytes)?;
+                    stdout.flush()?;
+                    Ok(bytes.len())
+                }
+            },
+            // Never truncate the config in place: a crash mid-write would leave it half written
+            Staged::File(temp) => self.commit_staged(&temp, bytes.len()).inspect_err(|_| { let _ = fs::remove_file(&temp); })
+        }
+    }
+
+    /// # Sync Parent
+    /// 
+    /// Flush a file's directory to disk, which makes a newly created or renamed file's directory entry durable (Unix only)
+    fn sync_parent(path: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        if let Some(parent) = path.parent() {
+            let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+            File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// # Write Target
+    /// 
+    /// The file a save actually replaces: the config's path, or the file it links to when it's a symlink,
+    /// so renaming into place updates the link's target instead of replacing the link itself
+    /// 
+    /// With `.with_nofollow()` a symlink is refused instead (Unix only), like opening it with `O_NOFOLLOW` would
+    fn write_target(&self) -> Result<PathBuf, FigConError> {
+        let mut target = self.location.clone();
+        for _ in 0..40 { // the usual limit on symlink hops, past which the OS reports a loop too
+            if !fs::symlink_metadata(&target).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+                return Ok(target);
+            }
+            #[cfg(unix)]
+            if self.nofollow {
+                return Err(io::Error::from_raw_os_error(libc::ELOOP).into());
+            }
+            let link = fs::read_link(&target)?;
+            target = match target.parent() {
+                Some(parent) => parent.join(link), // a relative link is relative to its own directory
+                None => link
+            };
+        }
+        Err(io::Error::new(ErrorKind::InvalidInput, "too many levels of symbolic links").into())
+    }
+
+    /// # Stage
+    /// 
+    /// Write serialized bytes to a new temporary file beside the config (`config.json.<pid>.<n>.tmp`), for `.save()` and
+    /// `FigConGroup::save_all()` to rename into place. The temporary file is created with the config's mode,
+    /// or else gets the permissions of the file it will replace, before any data is written to it
+    /// 
+    /// Nothing is staged for stdout and custom storage, which are written directly (`Staged::Direct`),
+    /// or when `.with_skip_unchanged_writes()` finds the bytes already stored (`Staged::Unchanged`; stdout is always written)
+    fn stage(&self, bytes: &[u8]) -> Result<Staged, FigConError> {
+        let stdio = self.storage.is_none() && is_stdio(&self.location);
+        if !stdio && self.skip_unchanged && self.is_saved(bytes) {
+            return Ok(Staged::Unchanged);
+        }
+        if stdio || self.storage.is_some() {
+            return Ok(Staged::Direct);
+        }
+        self.check_location()?;
+        let target = self.write_target()?;
+        let (temp, mut file) = self.create_staging_file(&target)?;
+        let permissions = match fs::metadata(&target) {
+            Ok(metadata) if self.mode.is_none() => file.set_permissions(metadata.permissions()), // before any data is written
+            _ => Ok(())
+        };
+        let written = permissions
+            .and_then(|_| file.write_all(bytes))
+            .and_then(|_| if self.fsync { file.sync_all() } else { Ok(()) });
+        if let Err(err) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(err.into());
+        }
+        Ok(Staged::File(temp))
+    }
+
+    /// # Commit Staged
+    /// 
+    /// Rename a file written by `.stage()` over the config (rotating the old one first when rotation applies),
+    /// returning how many bytes it holds
+    fn commit_staged(&self, temp: &Path, len: usize) -> Result<usize, FigConError> {
+        let target = self.write_target()?;
+        if let Some((max_bytes, keep)) = self.rotation && len as u64 > max_bytes && target.is_file() {
+            rotate::rotate(&target, keep)?;
+        }
+        fs::rename(temp, &target)?;
+        if self.fsync {
+            Self::sync_parent(&target)?;
+        }
+        Ok(len)
+    }
+
+    /// # Check Location
+    /// 
+    /// Catch misconfigured paths before writing, where the OS would only give a vague IO error
+    fn check_location(&self) -> Result<(), FigConError> {
+        if self.location.is_dir() {
+            return Err(FigConError::PathIsDirectory { path: self.location.clone() });
+        }
+        match self.location.parent() {
+            Some(parent) if parent.exists() && !parent.is_dir() => Err(FigConError::ParentNotDirectory { path: parent.to_owned() }),
+            _ => Ok(())
+        }
+    }
+
+    /// # Write Out (Retrying)
+    /// 
+    /// Write out the live config, retrying transient IO errors with exponential backoff when retries are enabled
+    fn write_out_retrying(&self) -> Result<usize, FigConError> {
+        let (attempts, mut backoff) = self.save_retries;
+        let mut result = self.write_out();
+        for _ in 0..attempts {
+            match &result {
+                Err(FigConError::Io(err)) if is_retryable(err.kind()) => {
+                    thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                    result = self.write_out();
+                },
+                _ => break
+            }
+        }
+        result
+    }
+
+    /// # Is Saved
+    /// 
+    /// Whether exactly these bytes are already what's stored, checking the file size before reading it
+    fn is_saved(&self, bytes: &[u8]) -> bool {
+        if let Some(storage) = &self.storage {
+            return storage.read().is_ok_and(|saved| saved == bytes);
+        }
+        let metadata = if self.nofollow { fs::symlink_metadata(&self.location) } else { fs::metadata(&self.location) };
+        metadata.is_ok_and(|metadata| metadata.is_file() && metadata.len() == bytes.len() as u64)
+            && fs::read(&self.location).is_ok_and(|saved| saved == bytes)
+    }
+
+    /// # Create Staging File
+    /// 
+    /// Create a new temporary file beside `target` to stage a save in (See `.open_staging_file()`), returning its path too.
+    /// A name that's already taken is skipped for the next one
+    fn create_staging_file(&self, target: &Path) -> Result<(PathBuf, File), FigConError> {
+        let mut attempts = 0;
+        loop {
+            let path = staging_path(target);
+            match self.open_staging_file(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(err) if err.kind() == ErrorKind::AlreadyExists && attempts < 16 => attempts += 1,
+                Err(err) => return Err(err.into())
+            }
+        }
+    }
+
+    /// # Open Staging File
+    /// 
+    /// Create a file that must not exist yet with the config's mode, for staging a save in
+    /// 
+    /// It's never opened through a symlink either (`O_NOFOLLOW` on unix, whatever `.with_nofollow()` says),
+    /// so nothing planted at its name can redirect the write
+    fn open_staging_file(&self, path: &Path) -> io::Result<File> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.custom_flags(libc::O_NOFOLLOW);
+            if let Some(mode) = self.mode {
+                options.mode(mode); // a new file never exists with looser permissions
+            }
+        }
+        let file = options.open(path)?;
+        #[cfg(unix)]
+        if let Some(mode) = self.mode {
+            use std::os::unix::fs::PermissionsExt;
+            // the umask, before any data is written
+            file.set_permissions(fs::Permissions::from_mode(mode)).inspect_err(|_| { let _ = fs::remove_file(path); })?;
+        }
+        Ok(file)
+    }
+
+    /// # With Skip Nulls
+    /// 
+    /// Leave out every object entry set to null when saving, at any depth (Off by default, since some schemas need explicit nulls)
+    /// 
+    /// Only the written file is filtered; the live config keeps its nulls
+    pub fn with_skip_nulls(mut self, enabled: bool) -> Self {
+        self.skip_nulls = enabled;
+        self
+    }
+
+    /// # With Fsync
+    /// 
+    /// Have `.save()` wait until the file (and on Unix, its directory) has actually reached the disk, so a saved config survives
+    /// a power failure or a hard crash (Off by default)
+    /// 
+    /// Without it, a save returns once the OS has the bytes, which may sit in its cache for a while before being written.
+    /// Syncing can take from milliseconds to far longer on slow or busy disks, so it suits configs that must never be lost
+    /// more than ones saved on every keystroke. Stdout and custom storage backends aren't affected
+    pub fn with_fsync(mut self, enabled: bool) -> Self {
+        self.fsync = enabled;
+        self
+    }
+
+    /// # With No-Follow
+    /// 
+    /// Refuse to save through a symlink: `.save()` fails if the final path component is a symlink
+    /// 
+    /// This guards daemons writing into shared directories against a planted symlink redirecting the write.
+    /// It is Unix-only (`O_NOFOLLOW`) and does nothing on other platforms
+    pub fn with_nofollow(mut self, enabled: bool) -> Self {
+        self.nofollow = enabled;
+        self
+    }
+
+    /// # With Strict Paths
+    /// 
+    /// Refuse to overwrite an object or array with a scalar (or null) through `set_key`, `set_key_path`, `set_many` and `set_obj`,
+    /// like `set_key_path("server", json!("localhost"))` wiping the whole `server` section (Off by default)
+    /// 
+    /// The `try_` variants return `FigConError::WouldReplaceContainer`, the plain setters panic.
+    /// Use `.set_key_path_force()` when replacing the section is intended. Merges and bulk rewrites (`merge`, `remap`, `overlay_env`, ...) are not checked
+    pub fn with_strict_paths(mut self, enabled: bool) -> Self {
+        self.strict_paths = enabled;
+        self
+    }
+
+    /// # With Path Separator
+    /// 
+    /// Separate the keys of paths with another character than `.`, like `/` or `:`, for configs whose keys contain dots
+    /// (`"log.level"` as a single key)
+    /// 
+    /// Every path-taking or path-producing method follows it: `get_key_path`, `set_key_path` and their variants, `set_if`'s target,
+    /// `import_under`, `namespace`, `remap`, `eq_ignoring`, `flatten`, `to_table`, `to_query_string`, `diff`, `pending_changes` and the env overlays
+    /// (where `__` then stands for the separator). Keys containing any character at all can still be reached by JSON Pointer, as in `set_if`
+    pub fn with_path_separator(mut self, sep: char) -> Self {
+        self.path_separator = sep;
+        self
+    }
+
+    /// # Top Key
+    /// 
+    /// The top-level key a path starts with
+    fn top_key<'a>(&self, path: &'a str) -> &'a str {
+        path.split(self.path_separator).next().unwrap_or(path)
+    }
+
+    /// # With Feature Namespace
+    /// 
+    /// Keep feature flags under another top-level key than `features`, for `is_enabled`, `enable` and `disable`.
+    /// The namespace may itself be a path, like `app.flags`
+    pub fn with_feature_namespace(mut self, namespace: &str) -> Self {
+        self.feature_namespace = namespace.to_owned();
+        self
+    }
+
+    /// # With Reload Interval
+    /// 
+    /// Reload at most once per interval through `.reload_or_keep()` (No limit by default), for callers reacting to
+    /// file change events: a burst of writes then costs one reload of the latest contents instead of one per event
+    pub fn with_reload_interval(mut self, interval: Duration) -> Self {
+        self.reload_interval = interval;
+        self
+    }
+
+    /// # With Skip Unchanged Writes
+    /// 
+    /// Make `.save()` compare the serialized config with what is already stored and skip the write if they're identical
+    /// (Off by default). This catches no-op saves the dirty flag can't, like setting a key to the value it already had
+    /// 
+    /// Each save then costs an extra size check, and a full read of the stored file whenever the sizes match.
+    /// Skipped saves still succeed and report 0 bytes to observers, and leave the file's mode alone
+    pub fn with_skip_unchanged_writes(mut self, enabled: bool) -> Self {
+        self.skip_unchanged = enabled;
+        self
+    }
+
+    /// # With Rotation
+    /// 
+    /// Keep older generations of a growing config: when a save would write more than `max_bytes`,
+    /// the current file is first kept as `config.1.json` (shifting `config.1.json` to `config.2.json`, and so on)
+    /// and the save writes a fresh file. Generations past `keep` are deleted
+    /// 
+    /// This works with the atomic save: the current file is hard-linked (or copied) to `config.1.json` while it stays in place,
+    /// then the new file is renamed over it, so there is never a moment with no config file at the path.
+    /// Custom storage backends are never rotated
+    pub fn with_rotation(mut self, max_bytes: u64, keep: usize) -> Self {
+        self.rotation = Some((max_bytes, keep));
+        self
+    }
+
+    /// # With Display Format
+    /// 
+    /// Choose whether `{}` prints the config prettified (the default) or compact on one line, which suits logs better
+    /// 
+    /// `{:#}` still prettifies either way, following the alternate flag convention
+    pub fn with_display_format(mut self, display_format: DisplayFormat) -> Self {
+        self.display_format = display_format;
+        self
+    }
+
+    /// # With Lenient Parsing
+    /// 
+    /// Let `.reload()` repair slightly malformed JSON (trailing commas, single-quoted strings) instead of panicking,
+    /// like `FigCon::try_load_lenient()` does for the first load
+    pub fn with_lenient_parsing(mut self, enabled: bool) -> Self {
+        self.lenient = enabled;
+        self
+    }
+
+    /// # Load Warning
+    /// 
+    /// What the last lenient load or reload had to work around, if anything
+    pub fn load_warning(&self) -> Option<&FigConWarning> {
+        self.load_warning.as_ref()
+    }
+
+    /// # With Mode
+    /// 
+    /// Set the Unix permission bits of the config file on every save, like `0o600` for configs holding secrets
+    /// 
+    /// Saves write a temporary file beside the config and rename it into place. That file is created with the mode
+    /// (or changed to it, if a stale one was left behind) before anything is written, so the contents are never readable
+    /// with looser permissions, not even for the moment before the rename. Does nothing on platforms without Unix permissions
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// # With Save Retries
+    /// 
+    /// Retry a failed save up to `attempts` more times, sleeping `backoff` before the first retry and doubling it each time
+    /// 
+    /// Only transient IO errors are retried: `Interrupted`, `WouldBlock`, `TimedOut` and `ResourceBusy`.
+    /// Anything else (like `PermissionDenied`) fails immediately. Useful for configs on NFS/SMB mounts
+    pub fn with_save_retries(mut self, attempts: u32, backoff: Duration) -> Self {
+        self.save_retries = (attempts, backoff);
+        self
+    }
+
+    /// # With Journal
+    /// 
+    /// Keep an append-only journal next to the config for write-heavy usage
+    /// 
+    /// Every `set_key` and `remove_key` (and their variants) appends one JSON line to the journal instead of
+    /// rewriting the whole file, panicking if the append fails. Any records already in the journal are
+    /// replayed onto the live config right away, and again on every `.reload()`
+    /// 
+    /// After a crash, the config is the last saved file plus every complete journal line; a torn final line is skipped.
+    /// Records are not fsynced, so a power loss can drop the most recent ones
+    /// 
+    /// Changes made through mutable references (`get_key_mut`, `new_obj`, ...) are not journaled,
+    /// so follow them with `.compact()` or `.save()`
+    pub fn with_journal(mut self, path: PathBuf) -> Self {
+        journal::replay(&path, &mut self.live_config).expect("Failed to replay config journal");
+        self.take_expiry();
+        self.journal = Some(path);
+        self
+    }
+
+    /// # With Audit Log
+    /// 
+    /// Keep an append-only audit trail of every mutation in a separate file, for compliance rather than recovery (Never replayed)
+    /// 
+    /// Every `set_key`, `remove_key`, `set_obj`, `set_key_path` and transaction edit (and their variants) buffers one JSON line
+    /// with `ts` (unix milliseconds), `op`, `key`, `old` and `new` fields, like `"op":"set","key":"port","old":8080,"new":9090`.
+    /// The buffered lines are appended in one batch on the next successful `.save()`, so enabling this adds IO to every save
+    /// 
+    /// Values of keys flagged with `mark_secret` are written as `"<redacted>"`.
+    /// Changes made through mutable references (`get_key_mut`, `new_obj`, ...) can't be seen and are never audited
+    pub fn with_audit_log(mut self, path: PathBuf) -> Self {
+        self.audit = Some(AuditLog::new(path));
+        self
+    }
+
+    /// # Audit
+    /// 
+    /// Buffer an audit line for a mutation if an audit log is enabled, redacting secret keys by their top-level key
+    fn audit(&self, op: &str, key: &str, old: Option<&Value>, new: Option<&Value>) {
+        if let Some(audit) = &self.audit {
+            audit.record(now_millis(), op, key, old, new, self.is_secret_st(self.top_key(key)));
+        }
+    }
+
+    /// # Compact Journal
+    /// 
+    /// Fold the journal into the main config file by saving it, then truncate the journal
+    /// 
+    /// If the save fails the journal is left untouched, so no records are lost
+    pub fn compact(&self) -> Result<(), FigConError> {
+        self.try_save()
+    }
+
+    /// # With Format
+    /// 
+    /// Choose the encoding `.save()` and `.reload()` use, without touching the live config
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// # With Codec
+    /// 
+    /// Encode and decode through a custom codec instead of the built-in format for `.save()`, `.reload()` and everything else
+    /// that touches the saved bytes, without touching the live config (Use `load_with_codec` to read a file through one)
+    /// 
+    /// Text output (`Display`, `.to_compact_string()`, ...) stays JSON, as it does for the binary formats
+    pub fn with_codec(mut self, codec: Box<dyn Codec>) -> Self {
+        self.codec = Some(Arc::new(DepthLimited(codec)));
+        self
+    }
+
+    /// # Mark Secret
+    /// 
+    /// Flag a key as holding a secret, so its values are redacted wherever the crate logs them
+    pub fn mark_secret(&mut self, key: String) {
+        if !self.secrets.contains(&key) {
+            self.secrets.push(key);
+        }
+    }
+
+    /// # Mark Secret (Static)
+    /// 
+    /// Flag a key as holding a secret, so its values are redacted wherever the crate logs them
+    pub fn mark_secret_st(&mut self, key: &str) {
+        self.mark_secret(key.to_owned());
+    }
+
+    /// # Is Secret
+    /// 
+    /// Check if a key was flagged with `mark_secret`
+    pub fn is_secret(&self, key: String) -> bool {
+        self.is_secret_st(&key)
+    }
+
+    /// # Is Secret (Static)
+    /// 
+    /// Check if a key was flagged with `mark_secret`
+    pub fn is_secret_st(&self, key: &str) -> bool {
+        self.secrets.iter().any(|secret| secret == key)
+    }
+
+    /// # With Secret Resolution
+    /// 
+    /// Resolve secret references in string values when they are read by `get_as` and `get_string`,
+    /// so the config file only ever stores the reference and never the secret itself
+    /// 
+    /// `"env:MY_TOKEN"` reads the environment variable `MY_TOKEN`, and with the `keyring` feature
+    /// `"keyring:service/account"` reads from the platform credential store. A reference that can't be resolved reads as missing.
+    /// Every other getter (like `get_key`) still returns the raw reference
+    pub fn with_secret_resolution(mut self, enabled: bool) -> Self {
+        self.secret_resolution = enabled;
+        self
+    }
+
+    /// # With Secret Resolver
+    /// 
+    /// Add a custom resolver for secret references, tried after the built-in ones
+    pub fn with_secret_resolver(mut self, resolver: Box<dyn SecretResolver>) -> Self {
+        self.resolvers.push(Arc::from(resolver));
+        self
+    }
+
+    /// # Resolve Secret
+    /// 
+    /// Turn a string read from the config into its secret if it is a reference, or leave it as-is otherwise
+    fn resolve_secret(&self, reference: &str) -> Option<Value> {
+        if !self.resolvers.iter().any(|resolver| resolver.handles(reference)) {
+            return Some(Value::String(reference.to_owned()));
+        }
+        self.resolvers.iter().find_map(|resolver| resolver.resolve(reference)).map(Value::String)
+    }
+
+    /// # With Change Logging
+    /// 
+    /// Log every mutation as a structured line through the `log` crate, like `config.set key=port old=8080 new=9090`
+    /// 
+    /// Lines go to the `figcon` target at `info` level, so they can be silenced with your logger's filter (e.g. `RUST_LOG=figcon=warn`)
+    /// or by turning this off. Values of keys flagged with `mark_secret` are written as `<redacted>`
+    #[cfg(feature = "log")]
+    pub fn with_change_logging(mut self, enabled: bool) -> Self {
+        self.change_logging = enabled;
+        self
+    }
+
+    /// # With Observer
+    /// 
+    /// Register an observer to be notified when the config is reloaded, saved, or fails to save
+    pub fn with_observer(mut self, observer: Box<dyn FigConObserver>) -> Self {
+        self.observer = Some(Arc::from(observer));
+        self
+    }
+
+    /// # With Trailing Newline
+    /// 
+    /// Choose whether `.save()` ends the file with a newline (On by default, as most editors and tools expect)
+    pub fn with_trailing_newline(mut self, enabled: bool) -> Self {
+        self.trailing_newline = enabled;
+        self
+    }
+
+    /// # Is Dirty
+    /// 
+    /// Returns true if the live config may have changed since it was loaded or last saved
+    /// 
+    /// Handing out a mutable reference (`get_key_mut`, `new_obj`, ...) counts as a change, since it can't be tracked further
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// # Modified Time
+    /// 
+    /// The last-modified time of the config file on disk, as reported by the file system
+    /// 
+    /// This only changes when the file is written (by this config or anyone else), unlike `.last_mutated()`
+    /// 
+    /// Will return None for configs backed by a custom storage or if the file's metadata is unavailable
+    pub fn modified_time(&self) -> Option<SystemTime> {
+        if self.storage.is_some() { return None; }
+        fs::metadata(&self.location).ok()?.modified().ok()
+    }
+
+    /// # Last Mutated
+    /// 
+    /// When the live config was last changed in memory, whether or not that change has been saved yet
+    /// 
+    /// Will return None if the config hasn't been changed since it was loaded (or on wasm32, which has no clock)
+    pub fn last_mutated(&self) -> Option<Instant> {
+        self.last_mutated
+    }
+
+    /// # Touch
+    /// 
+    /// Mark the live config as changed, right now
+    fn touch(&mut self) {
+        self.dirty.mark();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.last_mutated = Some(Instant::now());
+        }
+    }
+
+    /// # Transaction
+    /// 
+    /// Apply several edits that must all succeed or none apply
+    /// 
+    /// The closure edits a working copy through a FigConTxn. If it returns `Err`, the working copy is thrown away
+    /// and the config is left exactly as it was. Only a successful commit marks the config dirty and journals the edits.
+    /// With a journal, committing panics if the journal can't be appended to, like every other setter; use `.try_transaction()`
+    /// to get that failure back as an error instead
+    pub fn transaction<F, E>(&mut self, edit: F) -> Result<(), E> where F: FnOnce(&mut FigConTxn) -> Result<(), E> {
+        let mut txn = FigConTxn::new(self.live_config.clone(), self.path_separator, self.strict_paths);
+        edit(&mut txn)?;
+        self.commit(txn).expect("Failed to append to config journal");
+        Ok(())
+    }
+
+    /// # Try Transaction
+    /// 
+    /// Apply several edits that must all succeed or none apply, like `.transaction()`, but without ever panicking on commit
+    /// 
+    /// The closure's error type only has to convert from `FigConError`, so it can be `FigConError` itself and use `?` on
+    /// the `try_` setters of the FigConTxn. If the journal can't be appended to, the commit fails with `FigConError::Io`
+    /// and the config is left exactly as it was
+    pub fn try_transaction<F, E>(&mut self, edit: F) -> Result<(), E> where F: FnOnce(&mut FigConTxn) -> Result<(), E>, E: From<FigConError> {
+        let mut txn = FigConTxn::new(self.live_config.clone(), self.path_separator, self.strict_paths);
+        edit(&mut txn)?;
+        self.commit(txn)?;
+        Ok(())
+    }
+
+    /// # Commit
+    /// 
+    /// Journal, log and apply the edits of a finished transaction, journaling every touched key in a single append
+    /// before anything changes, so a journal failure leaves the config untouched
+    fn commit(&mut self, txn: FigConTxn) -> Result<(), FigConError> {
+        if txn.touched.is_empty() { return Ok(()); }
+        if let Some(journal) = &self.journal {
+            let mut records: Vec<Value> = txn.touched.iter().map(|key| match txn.working.get_key_st(key) {
+                Some(value) => journal::set_record(key, value),
+                None => journal::del_record(key)
+            }).collect();
+            if txn.touched.iter().any(|key| self.expires.contains_key(key)) {
+                let mut expires = self.expires.clone();
+                expires.retain(|key, _| !txn.touched.contains(key));
+                records.push(Self::expiry_record(&expires));
+            }
+            journal::append(journal, &records)?;
+        }
+        #[cfg(feature = "log")]
+        if self.change_logging {
+            for key in &txn.touched {
+                let new = txn.working.get_key_st(key);
+                changelog::record(if new.is_some() { "set" } else { "del" }, key, self.live_config.get_key_st(key), new, self.is_secret_st(key));
+            }
+        }
+        for key in &txn.touched {
+            let new = txn.working.get_key_st(key);
+            self.audit(if new.is_some() { "set" } else { "del" }, key, self.live_config.get_key_st(key), new);
+        }
+        self.live_config = txn.working;
+        self.expires.retain(|key, _| !txn.touched.contains(key)); // edited keys become permanent
+        self.touch();
+        self.notify_change(&txn.touched);
+        Ok(())
+    }
+
+    /// # Commented Template
+    /// 
+    /// Generate a JSONC starter config from the default value of a struct, with `// comment` lines above the given top-level keys
+    /// 
+    /// This is write-only: plain JSON can't hold comments, so they are stripped if the template is ever loaded and saved again
+    pub fn template_from<T: Serialize + Default>(comments: &[(&str, &str)]) -> String {
+        let value = serde_json::to_value(T::default()).expect("Default struct serialization failed");
+        let Some(object) = value.obj() else {
+            return serde_json::to_string_pretty(&value).unwrap();
+        };
+        if object.is_empty() { return "{}".to_owned(); }
+        let mut template = String::from("{\n");
+        for (index, (key, value)) in object.iter().enumerate() {
+            for (_, comment) in comments.iter().filter(|(target, _)| target == key) {
+                for line in comment.lines() {
+                    template.push_str(&format!("  // {}\n", line));
+                }
+            }
+            let pretty = serde_json::to_string_pretty(value).unwrap().replace('\n', "\n  ");
+            template.push_str(&format!("  {}: {}", Value::String(key.clone()), pretty));
+            template.push_str(if index + 1 < object.len() { ",\n" } else { "\n" });
+        }
+        template.push('}');
+        template
+    }
+
+    /// # As Object
+    /// 
+    /// Return the raw serde_json map behind the config for anything the FigCon API doesn't cover
+    /// 
+    /// Will return None if the loaded config isn't an object
+    pub fn as_object(&self) -> Option<&Map<String, Value>> {
+        self.live_config.obj()
+    }
+
+    /// # As Object (Mutable)
+    /// 
+    /// Return the raw serde_json map behind the config for anything the FigCon API doesn't cover, marking the config dirty
+    /// 
+    /// Will return None if the loaded config isn't an object. Edits made through the map are not journaled
+    pub fn as_object_mut(&mut self) -> Option<&mut Map<String, Value>> {
+        self.touch();
+        self.live_config.obj_mut()
+    }
+
+    /// # Root
+    /// 
+    /// Whether the config's top-level value is an object, an array or a scalar
+    pub fn root(&self) -> FigConRoot {
+        FigConRoot::of(&self.live_config)
+    }
+
+    /// # Root Kind
+    /// 
+    /// The exact type of the config's top-level value, telling the scalar types apart unlike `.root()`
+    /// 
+    /// Lets code check the shape before calling object-only or array-only methods, without cloning the value out
+    pub fn root_kind(&self) -> FigConRootKind {
+        FigConRootKind::of(&self.live_config)
+    }
+
+    /// # Require Object Root
+    /// 
+    /// Fail with `FigConError::WrongRoot` unless the config is rooted in an object, as key methods need
+    fn require_object_root(&self) -> Result<(), FigConError> {
+        match &self.live_config {
+            Value::Object(_) => Ok(()),
+            other => Err(FigConError::WrongRoot { expected: FigConRoot::Object, found: FigConRoot::of(other) })
+        }
+    }
+
+    /// # As Array
+    /// 
+    /// Borrow the elements of an array-rooted config, or fail with `FigConError::WrongRoot`
+    fn as_array(&self) -> Result<&Vec<Value>, FigConError> {
+        match &self.live_config {
+            Value::Array(items) => Ok(items),
+            other => Err(FigConError::WrongRoot { expected: FigConRoot::Array, found: FigConRoot::of(other) })
+        }
+    }
+
+    /// # As Array (Mutable)
+    /// 
+    /// Mutably borrow the elements of an array-rooted config, or fail with `FigConError::WrongRoot`
+    fn as_array_mut(&mut self) -> Result<&mut Vec<Value>, FigConError> {
+        match &mut self.live_config {
+            Value::Array(items) => Ok(items),
+            other => Err(FigConError::WrongRoot { expected: FigConRoot::Array, found: FigConRoot::of(other) })
+        }
+    }
+
+    /// # Push
+    /// 
+    /// Append an element to an array-rooted config
+    /// 
+    /// Fails with `FigConError::WrongRoot` if the root isn't an array, and `FigConError::TooDeep` for a value nesting past `FigCon::MAX_DEPTH`.
+    /// Index edits are not journaled
+    pub fn push(&mut self, value: Value) -> Result<(), FigConError> {
+        check_depth(&value, 1)?;
+        let items = self.as_array_mut()?;
+        items.push(value);
+        let index = items.len() - 1;
+        self.touch();
+        self.notify_change(&[index.to_string()]);
+        Ok(())
+    }
+
+    /// # Get Index
+    /// 
+    /// Acquire an element of an array-rooted config, or None if the index is out of bounds
+    /// 
+    /// Fails with `FigConError::WrongRoot` if the root isn't an array
+    pub fn get_index(&self, index: usize) -> Result<Option<&Value>, FigConError> {
+        Ok(self.as_array()?.get(index))
+    }
+
+    /// # Set Index
+    /// 
+    /// Replace an element of an array-rooted config (Use `.push()` to add new elements)
+    /// 
+    /// Fails with `FigConError::WrongRoot` if the root isn't an array, and `FigConError::IndexOutOfBounds` past its end.
+    /// Index edits are not journaled
+    pub fn set_index(&mut self, index: usize, value: Value) -> Result<(), FigConError> {
+        check_depth(&value, 1)?;
+        let items = self.as_array_mut()?;
+        let len = items.len();
+        let Some(item) = items.get_mut(index) else {
+            return Err(FigConError::IndexOutOfBounds { index, len });
+        };
+        *item = value;
+        self.touch();
+        self.notify_change(&[index.to_string()]);
+        Ok(())
+    }
+
+    /// # Length
+    /// 
+    /// The number of elements in an array-rooted config
+    /// 
+    /// Fails with `FigConError::WrongRoot` if the root isn't an array
+    pub fn len(&self) -> Result<usize, FigConError> {
+        Ok(self.as_array()?.len())
+    }
+
+    /// # Is Empty
+    /// 
+    /// Whether an array-rooted config has no elements
+    /// 
+    /// Fails with `FigConError::WrongRoot` if the root isn't an array
+    pub fn is_empty(&self) -> Result<bool, FigConError> {
+        Ok(self.as_array()?.is_empty())
+    }
+
+    /// # Any Keys
+    /// 
+    /// Returns true if the object contains any keys (Length > 0)
+    /// 
+    /// Automatically returns false if used on non-objects
+    pub fn any_keys(&self) -> bool {
+        self.live_config.any_keys()
+    }
+
+    /// # List Keys
+    /// 
+    /// Attempts to return all keys within an object
+    pub fn list_keys(&self) -> Option<Vec<String>> {
+        self.live_config.list_keys()
+    }
+
+    /// # Keys Sorted
+    /// 
+    /// Return all top-level keys in alphabetical (byte-wise) order, whatever order the map keeps them in
+    /// 
+    /// Returns an empty list if the config isn't an object
+    pub fn keys_sorted(&self) -> Vec<String> {
+        self.keys_by(str::cmp)
+    }
+
+    /// # Keys By
+    /// 
+    /// Return all top-level keys sorted with a custom comparison (Group settings for display, put favorites first, ...)
+    /// 
+    /// Returns an empty list if the config isn't an object
+    pub fn keys_by<F: Fn(&str, &str) -> Ordering>(&self, cmp: F) -> Vec<String> {
+        let mut keys = self.list_keys().unwrap_or_default();
+        keys.sort_by(|left, right| cmp(left, right));
+        keys
+    }
+
+    /// # List Values
+    /// 
+    /// Attempts to return all values within an object
+    pub fn list_values(&self) -> Option<Vec<Value>> {
+        self.live_config.list_values()
+    }
+
+    /// # Iterate Keys
+    /// 
+    /// Attempts to return an iterator for the keys within the current object.
+    pub fn iter_keys(&mut self) -> Option<Keys<'_>> {
+        self.live_config.iter_keys()
+    }
+
+    /// # Iterate Values
+    /// 
+    /// Attempts to return an iterator for the values within the current object.
+    pub fn iter_values(&mut self) -> Option<Values<'_>> {
+        self.live_config.iter_values()
+    }
+
+    /// # Get Key (Mutable)
+    /// 
+    /// Acquire a key's value within an object within a value
+    /// 
+    /// Will return None if used on non-objects. Edits made through the reference are not journaled
+    pub fn get_key_mut(&mut self, key: String) -> Option<&mut Value> {
+        self.get_key_mut_st(&key)
+    }
+
+    /// # Get Key (Mutable, Static)
+    /// 
+    /// Acquire a key's value within an object within a value
+    /// 
+    /// Will return None if used on non-objects. Edits made through the reference are not journaled
+    pub fn get_key_mut_st(&mut self, key: &str) -> Option<&mut Value> {
+        self.touch();
+        self.live_config.get_key_mut_st(key)
+    }
+
+    /// # Get Key
+    /// 
+    /// Acquire a key's value within an object within a value
+    /// 
+    /// Will return None if used on non-objects
+    pub fn get_key(&self, key: String) -> Option<&Value> {
+        self.get_key_st(&key)
+    }
+
+    /// # Get Key (Static)
+    /// 
+    /// Acquire a key's value within an object within a value
+    /// 
+    /// Will return None if used on non-objects
+    pub fn get_key_st(&self, key: &str) -> Option<&Value> {
+        self.source(key).get_key_st(key)
+    }
+
+    /// # Get Key As
+    /// 
+    /// Acquire a key's value deserialized into `T`, falling back to its registered default
+    /// 
+    /// Will return None if the key is missing or the value doesn't fit `T`
+    pub fn get_as<T: DeserializeOwned>(&self, key: String) -> Option<T> {
+        self.get_as_st(&key)
+    }
+
+    /// # Get Key As (Static)
+    /// 
+    /// Acquire a key's value deserialized into `T`, falling back to its registered default
+    /// 
+    /// Will return None if the key is missing or the value doesn't fit `T`
+    pub fn get_as_st<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        if self.secret_resolution && let Some(Value::String(reference)) = self.get_key_st(key) {
+            return serde_json::from_value(self.resolve_secret(reference)?).ok();
+        }
+        self.source(key).get_as_st(key)
+    }
+
+    /// # Register Default
+    /// 
+    /// Register a fallback value for a key, kept separately from the live config and never saved
+    /// 
+    /// Lookup precedence: the live config's value if the key exists there, otherwise the registered default.
+    /// Every read-only getter (`get_key`, `get_as`, `get_obj`, `get_bytes`, `get_f64`, ...) falls back,
+    /// while `has_key`, `list_keys` and the mutable getters only ever see the live config
+    pub fn register_default(&mut self, key: String, value: Value) {
+        self.defaults.set_key(key, value);
+    }
+
+    /// # Register Default (Static)
+    /// 
+    /// Register a fallback value for a key, kept separately from the live config and never saved
+    pub fn register_default_st(&mut self, key: &str, value: Value) {
+        self.register_default(key.to_owned(), value);
+    }
+
+    /// # Apply Registered Defaults
+    /// 
+    /// Copy every registered default whose key is missing from the live config into it, so the next save persists them
+    pub fn apply_registered_defaults(&mut self) {
+        if let Some(defaults) = self.defaults.obj() {
+            for (key, value) in defaults.clone() {
+                if !self.live_config.has_key(key.clone()) {
+                    self.set_key(key, value);
+                }
+            }
+        }
+    }
+
+    /// # Source
+    /// 
+    /// The value a read of `key` should come from: the live config if it has the key, otherwise the registered defaults
+    fn source(&self, key: &str) -> &Value {
+        if self.live_config.has_key_st(key) && !self.is_expired(key) { &self.live_config } else { &self.defaults }
+    }
+
+    /// # Set With TTL
+    /// 
+    /// Assign a key's value that expires after `ttl`, for cache-like configs
+    /// 
+    /// Expiry times are kept beside the config rather than among its keys, and saved under the reserved `__expires` key
+    /// so they survive saving and reloading (They are journaled too). Once expired, reads treat the key as missing
+    /// (falling back to registered defaults) and saves leave it out of the file; `.purge_expired()` also drops it from memory.
+    /// Any other write to the key (`set_key`, `set_key_path`, a merge, ...) makes it permanent. Nothing expires on wasm32, which has no clock
+    /// 
+    /// Will do nothing if used on non-objects, and panics where `try_set_key` would otherwise fail
+    pub fn set_with_ttl(&mut self, key: &str, value: Value, ttl: Duration) {
+        if !self.live_config.is_object() { return; }
+        self.try_set_key_st(key, value).expect("Refused to set config key");
+        let at = now_millis().saturating_add(ttl.as_millis().try_into().unwrap_or(u64::MAX));
+        self.expires.insert(key.to_owned(), at);
+        self.journal_expiry();
+    }
+
+    /// # Purge Expired
+    /// 
+    /// Remove every key whose TTL has run out, returning the keys removed
+    pub fn purge_expired(&mut self) -> Vec<String> {
+        let now = now_millis();
+        let stale: Vec<String> = self.expires.iter().filter(|(_, at)| **at <= now).map(|(key, _)| key.clone()).collect();
+        if stale.is_empty() { return Vec::new(); }
+        self.expires.retain(|_, at| *at > now);
+        self.journal_expiry();
+        let removed: Vec<String> = stale.into_iter().filter(|key| self.apply_remove(key).is_some()).collect();
+        self.touch(); // the expiry times changed even if no key was left to remove
+        self.notify_change(&removed);
+        removed
+    }
+
+    /// # Is Expired
+    /// 
+    /// Whether `key` was set with a TTL that has run out
+    fn is_expired(&self, key: &str) -> bool {
+        self.expires.get(key).is_some_and(|at| *at <= now_millis())
+    }
+
+    /// # Forget Expiry
+    /// 
+    /// Forget the TTL of a key, if it has one, making it permanent
+    fn forget_expiry(&mut self, key: &str) {
+        if self.expires.remove(key).is_some() {
+            self.journal_expiry();
+        }
+    }
+
+    /// # Journal Expiry
+    /// 
+    /// Journal the expiry times as a whole, under `__expires` like in the saved file
+    fn journal_expiry(&self) {
+        let Some(journal) = &self.journal else { return; };
+        journal::append(journal, &[Self::expiry_record(&self.expires)]).expect("Failed to append to config journal");
+    }
+
+    /// # Expiry Record
+    /// 
+    /// A journal record replacing every expiry time with `expires`
+    fn expiry_record(expires: &BTreeMap<String, u64>) -> Value {
+        if expires.is_empty() {
+            return journal::del_record(EXPIRES_KEY);
+        }
+        journal::set_record(EXPIRES_KEY, &Value::Object(expires.iter().map(|(key, at)| (key.clone(), json!(at))).collect()))
+    }
+
+    /// # Set Key
+    /// 
+    /// Assign a key's value within an object within a value
+    /// 
+    /// Will do nothing if used on non-objects, without marking the config dirty. Panics where `try_set_key` would otherwise fail
+    /// (With `.with_strict_paths(true)`, or for a value nesting past `FigCon::MAX_DEPTH`)
+    pub fn set_key(&mut self, key: String, value: Value) {
+        if !self.live_config.is_object() { return; }
+        self.try_set_key(key, value).expect("Refused to set config key");
+    }
+
+    /// # Try Set Key
+    /// 
+    /// Assign a key's value, returning `FigConError::WouldReplaceContainer` instead of overwriting
+    /// a whole object or array with a scalar when `.with_strict_paths(true)` is on,
+    /// `FigConError::TooDeep` for a value that would nest past `FigCon::MAX_DEPTH`,
+    /// and `FigConError::WrongRoot` if the config isn't rooted in an object
+    pub fn try_set_key(&mut self, key: String, value: Value) -> Result<(), FigConError> {
+        self.require_object_root()?;
+        check_depth(&value, 1)?;
+        check_replace(self.strict_paths, self.live_config.get_key_st(&key), &value, &key)?;
+        self.apply_set(&key, value);
+        self.touch();
+        self.notify_change(&[key]);
+        Ok(())
+    }
+
+    /// # Try Set Key (Static)
+    /// 
+    /// Assign a key's value, failing like `try_set_key`
+    pub fn try_set_key_st(&mut self, key: &str, value: Value) -> Result<(), FigConError> {
+        self.try_set_key(key.to_owned(), value)
+    }
+
+    /// # Set Many
+    /// 
+    /// Assign several keys as one unit, in the order given
+    /// 
+    /// The config is marked dirty once and observers get a single `on_change` with every affected key,
+    /// so reactive consumers never see the intermediate states
+    /// 
+    /// Will do nothing if used on non-objects. Panics where `try_set_many` would otherwise fail, before any key is set
+    pub fn set_many(&mut self, entries: Vec<(String, Value)>) {
+        if entries.is_empty() || !self.live_config.is_object() { return; }
+        self.try_set_many(entries).expect("Refused to set config keys");
+    }
+
+    /// # Try Set Many
+    /// 
+    /// Assign several keys as one unit like `set_many`, checking every entry like `try_set_key` first:
+    /// if any of them would fail, the error is returned and none of them are set
+    pub fn try_set_many(&mut self, entries: Vec<(String, Value)>) -> Result<(), FigConError> {
+        self.require_object_root()?;
+        for (index, (key, value)) in entries.iter().enumerate() {
+            check_depth(value, 1)?;
+            // A key given twice replaces its own earlier entry rather than what's in the config
+            let existing = entries[..index].iter().rev().find(|(earlier, _)| earlier == key).map(|(_, value)| value);
+            check_replace(self.strict_paths, existing.or_else(|| self.live_config.get_key_st(key)), value, key)?;
+        }
+        if entries.is_empty() { return Ok(()); }
+        let mut keys = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            self.apply_set(&key, value);
+            keys.push(key);
+        }
+        self.touch();
+        self.notify_change(&keys);
+        Ok(())
+    }
+
+    /// # Merge
+    /// 
+    /// Deep-merge an object into the config: nested objects are combined key by key,
+    /// arrays are combined according to `strategy`, and everything else is overwritten
+    /// 
+    /// Every top-level key the overlay touches is journaled and reported to observers in a single `on_change`.
+    /// Will do nothing if the overlay isn't an object
+    pub fn merge(&mut self, overlay: Value, strategy: ArrayMergeStrategy) {
+        self.merge_overlay(overlay, strategy, false);
+    }
+
+    /// # Patch
+    /// 
+    /// Like `.merge()`, but a null in the patch removes that key instead of storing null (JSON Merge Patch, RFC 7396)
+    /// 
+    /// Will do nothing if the patch isn't an object
+    pub fn patch(&mut self, patch: Value, strategy: ArrayMergeStrategy) {
+        self.merge_overlay(patch, strategy, true);
+    }
+
+    /// # Merge With
+    /// 
+    /// Deep-merge another config into this one, asking `resolver` what to keep wherever both have a different value for the same setting
+    /// 
+    /// The resolver gets the conflict's path (with the path separator between keys) and the current and incoming values,
+    /// and returns the value to store, like keeping the larger of two numbers. Nested objects are combined key by key
+    /// and settings only the other config has are copied over without asking; anything else (arrays included) counts as a conflict.
+    /// Top-level keys that end up changed are journaled and reported to observers in a single `on_change`
+    pub fn merge_with<F: FnMut(&str, &Value, &Value) -> Value>(&mut self, other: &FigCon, mut resolver: F) {
+        let Value::Object(overlay) = &other.live_config else { return; };
+        if !self.live_config.is_object() { return; }
+        let mut keys = Vec::with_capacity(overlay.len());
+        for (key, value) in overlay {
+            let current = self.live_config.get_key_st(key);
+            let mut merged = match current {
+                Some(current) => current.clone(),
+                None => value.clone()
+            };
+            merge::resolve_into(&mut merged, value, key, self.path_separator, &mut resolver, 1);
+            if current == Some(&merged) { continue; }
+            self.apply_set(key, merged);
+            keys.push(key.clone());
+        }
+        if !keys.is_empty() {
+            self.touch();
+            self.notify_change(&keys);
+        }
+    }
+
+    /// # Merge Overlay
+    /// 
+    /// Shared body of `.merge()` and `.patch()`, applied one top-level key at a time
+    fn merge_overlay(&mut self, overlay: Value, strategy: ArrayMergeStrategy, remove_nulls: bool) {
+        let Value::Object(overlay) = overlay else { return; };
+        if !self.live_config.is_object() { return; }
+        let mut keys = Vec::with_capacity(overlay.len());
+        for (key, value) in overlay {
+            if remove_nulls && value.is_null() {
+                if self.apply_remove(&key).is_none() { continue; }
+            } else {
+                let mut merged = self.live_config.get_key_st(&key).cloned().unwrap_or(Value::Null);
+                merge::merge_into(&mut merged, value, strategy, remove_nulls, 1);
+                self.apply_set(&key, merged);
+            }
+            keys.push(key);
+        }
+        if !keys.is_empty() {
+            self.touch();
+            self.notify_change(&keys);
+        }
+    }
+
+    /// # Apply Set
+    /// 
+    /// Log, journal and assign a key, making it permanent, and leaving the dirty flag and observers to the caller
+    /// 
+    /// Panics if the value would nest past `FigCon::MAX_DEPTH`
+    fn apply_set(&mut self, key: &str, value: Value) {
+        check_depth(&value, 1).expect("Refused to set config key");
+        self.forget_expiry(key);
+        #[cfg(feature = "log")]
+        if self.change_logging {
+            changelog::record("set", key, self.live_config.get_key_st(key), Some(&value), self.is_secret_st(key));
+        }
+        if let Some(journal) = &self.journal {
+            journal::append_set(journal, key, &value).expect("Failed to append to config journal");
+        }
+        self.audit("set", key, self.live_config.get_key_st(key), Some(&value));
+        self.live_config.set_key_st(key, value);
+    }
+
+    /// # Notify Change
+    /// 
+    /// Tell the observer (if any) which keys just changed
+    fn notify_change(&self, keys: &[String]) {
+        if let Some(observer) = &self.observer {
+            observer.on_change(keys);
+        }
+    }
+
+    /// # Set Key (Static)
+    /// 
+    /// Assign a key's value within an object within a value
+    /// 
+    /// Will do nothing if used on non-objects
+    pub fn set_key_st(&mut self, key: &str, value: Value) {
+        self.set_key(key.to_owned(), value);
+    }
+
+    /// # Set String
+    /// 
+    /// Assign a key a string value
+    pub fn set_str(&mut self, key: String, value: &str) {
+        self.set_key(key, Value::from(value));
+    }
+
+    /// # Set String (Static)
+    /// 
+    /// Assign a key a string value
+    pub fn set_str_st(&mut self, key: &str, value: &str) {
+        self.set_str(key.to_owned(), value);
+    }
+
+    /// # Set Bool
+    /// 
+    /// Assign a key a boolean value
+    pub fn set_bool(&mut self, key: String, value: bool) {
+        self.set_key(key, Value::Bool(value));
+    }
+
+    /// # Set Bool (Static)
+    /// 
+    /// Assign a key a boolean value
+    pub fn set_bool_st(&mut self, key: &str, value: bool) {
+        self.set_bool(key.to_owned(), value);
+    }
+
+    /// # Toggle
+    /// 
+    /// Flip a boolean setting and return its new value (For on/off switches in settings UIs)
+    /// 
+    /// The current value is read like `get_bool`, so defaults count and `"yes"`, `"off"`, `1` and the like are coerced.
+    /// A missing key, or a value that can't be coerced to a boolean, counts as false and becomes true. Always stores a real boolean
+    /// 
+    /// Fails like `try_set_key` when the new value can't be stored (`FigConError::WrongRoot` for a config not rooted in an object,
+    /// or `FigConError::WouldReplaceContainer` under strict paths), leaving the setting as it was
+    pub fn toggle(&mut self, key: String) -> Result<bool, FigConError> {
+        let value = !self.get_bool_st(&key).unwrap_or(false);
+        self.try_set_key(key, Value::Bool(value))?;
+        Ok(value)
+    }
+
+    /// # Toggle (Static)
+    /// 
+    /// Flip a boolean setting and return its new value, failing like `toggle`
+    pub fn toggle_st(&mut self, key: &str) -> Result<bool, FigConError> {
+        self.toggle(key.to_owned())
+    }
+
+    /// # Is Enabled
+    /// 
+    /// Whether a feature flag is on, read as a boolean under the feature namespace (`features.dark_mode` for `dark_mode`)
+    /// 
+    /// Values are coerced like `get_bool` and defaults count. A missing flag, or one that can't be coerced, is off
+    pub fn is_enabled(&self, flag: &str) -> bool {
+        self.get_key_path(&self.feature_path(flag)).and_then(coerce::to_bool).unwrap_or(false)
+    }
+
+    /// # Enable
+    /// 
+    /// Turn a feature flag on, storing `true` under the feature namespace
+    /// 
+    /// Does nothing (and doesn't mark the config dirty) if the flag is already stored as `true`
+    pub fn enable(&mut self, flag: &str) {
+        self.set_feature(flag, true);
+    }
+
+    /// # Disable
+    /// 
+    /// Turn a feature flag off, storing `false` under the feature namespace
+    /// 
+    /// Does nothing (and doesn't mark the config dirty) if the flag is already stored as `false`
+    pub fn disable(&mut self, flag: &str) {
+        self.set_feature(flag, false);
+    }
+
+    /// # Feature Path
+    /// 
+    /// Where a feature flag is stored
+    fn feature_path(&self, flag: &str) -> String {
+        format!("{}{}{}", self.feature_namespace, self.path_separator, flag)
+    }
+
+    /// # Set Feature
+    /// 
+    /// Store a feature flag unless it already holds that value
+    fn set_feature(&mut self, flag: &str, enabled: bool) {
+        if !self.live_config.is_object() { return; }
+        let path = self.feature_path(flag);
+        if path::get_path(&self.live_config, &path, self.path_separator) == Some(&Value::Bool(enabled)) { return; }
+        self.set_key_path_force(&path, Value::Bool(enabled));
+    }
+
+    /// # Set i64
+    /// 
+    /// Assign a key an integer value, kept exact all the way out to `i64::MIN` and `i64::MAX`
+    pub fn set_i64(&mut self, key: String, value: i64) {
+        self.set_key(key, Value::from(value));
+    }
+
+    /// # Set i64 (Static)
+    /// 
+    /// Assign a key an integer value, kept exact all the way out to `i64::MIN` and `i64::MAX`
+    pub fn set_i64_st(&mut self, key: &str, value: i64) {
+        self.set_i64(key.to_owned(), value);
+    }
+
+    /// # Set u64
+    /// 
+    /// Assign a key an unsigned integer value, kept exact all the way up to `u64::MAX`
+    pub fn set_u64(&mut self, key: String, value: u64) {
+        self.set_key(key, Value::from(value));
+    }
+
+    /// # Set u64 (Static)
+    /// 
+    /// Assign a key an unsigned integer value, kept exact all the way up to `u64::MAX`
+    pub fn set_u64_st(&mut self, key: &str, value: u64) {
+        self.set_u64(key.to_owned(), value);
+    }
+
+    /// # Set f64
+    /// 
+    /// Assign a key a floating point value
+    /// 
+    /// JSON has no NaN or infinity, so non-finite values return `FigConError::UnrepresentableNumber` and leave the key untouched
+    /// instead of silently turning into null. Store them as strings (`set_str(key, "NaN")`) if they're truly needed
+    pub fn set_f64(&mut self, key: String, value: f64) -> Result<(), FigConError> {
+        if !value.is_finite() {
+            return Err(FigConError::UnrepresentableNumber { key, value });
+        }
+        self.set_key(key, Value::from(value));
+        Ok(())
+    }
+
+    /// # Set f64 (Static)
+    /// 
+    /// Assign a key a floating point value
+    /// 
+    /// JSON has no NaN or infinity, so non-finite values return `FigConError::UnrepresentableNumber` and leave the key untouched
+    /// instead of silently turning into null. Store them as strings (`set_str(key, "NaN")`) if they're truly needed
+    pub fn set_f64_st(&mut self, key: &str, value: f64) -> Result<(), FigConError> {
+        self.set_f64(key.to_owned(), value)
+    }
+
+    /// # Set Array
+    /// 
+    /// Assign a key an array serialized from a slice of any serializable items
+    /// 
+    /// Will return an error if an item fails to serialize
+    pub fn set_array<T: Serialize>(&mut self, key: String, items: &[T]) -> Result<(), FigConError> {
+        let value = serde_json::to_value(items).map_err(FigConError::Serialize)?;
+        self.try_set_key(key, value)
+    }
+
+    /// # Set Array (Static)
+    /// 
+    /// Assign a key an array serialized from a slice of any serializable items
+    /// 
+    /// Will return an error if an item fails to serialize
+    pub fn set_array_st<T: Serialize>(&mut self, key: &str, items: &[T]) -> Result<(), FigConError> {
+        self.set_array(key.to_owned(), items)
+    }
+
+    /// # Import Under
+    /// 
+    /// Serialize any data (a `HashMap`, a struct, ...) and store it at the dot-path `namespace`, like `"env"` or `"plugins.audio"`
+    /// 
+    /// This overwrites the namespace wholesale: whatever was there before is replaced, not merged (Use `.merge()` to combine).
+    /// Will return an error if the data fails to serialize, or where `.try_set_key_path_force()` would fail, leaving the config untouched
+    pub fn import_under<T: Serialize>(&mut self, namespace: &str, data: &T) -> Result<(), FigConError> {
+        let value = serde_json::to_value(data).map_err(FigConError::Serialize)?;
+        self.try_set_key_path_force(namespace, value)
+    }
+
+    /// # Compare and Set
+    /// 
+    /// Set a key to `new` only if its current value equals `expected`, returning whether the swap happened
+    /// 
+    /// A missing key never matches. This is only atomic in memory: to coordinate with other processes
+    /// sharing the file, hold a lock around `.reload()`, this call and `.save()`
+    pub fn compare_and_set(&mut self, key: String, expected: &Value, new: Value) -> bool {
+        if self.live_config.get_key_st(&key) != Some(expected) { return false; }
+        self.set_key(key, new);
+        true
+    }
+
+    /// # Compare and Set (Static)
+    /// 
+    /// Set a key to `new` only if its current value equals `expected`, returning whether the swap happened
+    /// 
+    /// A missing key never matches. This is only atomic in memory, not across processes
+    pub fn compare_and_set_st(&mut self, key: &str, expected: &Value, new: Value) -> bool {
+        self.compare_and_set(key.to_owned(), expected, new)
+    }
+
+    /// # Deduplicate Array
+    /// 
+    /// Remove repeated elements from the array at a key, keeping the first occurrence of each in order
+    /// 
+    /// Elements are compared by value, so nested objects dedup structurally. Returns how many elements were removed;
+    /// missing keys and non-array values are left alone and return 0. Only marks the config dirty if something was removed
+    pub fn array_dedup(&mut self, key: String) -> usize {
+        let Some(Value::Array(items)) = self.live_config.get_key_st(&key) else { return 0; };
+        let mut unique: Vec<Value> = Vec::with_capacity(items.len());
+        for item in items {
+            if !unique.contains(item) {
+                unique.push(item.clone());
+            }
+        }
+        let removed = items.len() - unique.len();
+        if removed > 0 {
+            self.set_key(key, Value::Array(unique));
+        }
+        removed
+    }
+
+    /// # Deduplicate Array (Static)
+    /// 
+    /// Remove repeated elements from the array at a key, keeping the first occurrence of each in order
+    /// 
+    /// Returns how many elements were removed; missing keys and non-array values are left alone and return 0
+    pub fn array_dedup_st(&mut self, key: &str) -> usize {
+        self.array_dedup(key.to_owned())
+    }
+
+    /// # Has Key
+    /// 
+    /// Check if a key exists within an object within a value
+    /// 
+    /// Automatically returns false if used on non-objects
+    pub fn has_key(&self, key: String) -> bool {
+        self.has_key_st(&key)
+    }
+
+    /// # Has Key
+    /// 
+    /// Check if a key exists within an object within a value
+    /// 
+    /// Automatically returns false if used on non-objects
+    pub fn has_key_st(&self, key: &str) -> bool {
+        self.live_config.has_key_st(key) && !self.is_expired(key)
+    }
+
+    /// # Remove and Get Key
+    /// 
+    /// Remove an object's key within a value and return it if it exists
+    /// 
+    /// Will return None if used on non-objects
+    pub fn remove_get_key(&mut self, key: String) -> Option<Value> {
+        self.remove_get_key_st(&key)
+    }
+
+    /// # Apply Remove
+    /// 
+    /// Journal, remove and log a key, leaving the dirty flag and observers to the caller
+    /// 
+    /// Does nothing (not even journaling) if there is no such key
+    fn apply_remove(&mut self, key: &str) -> Option<Value> {
+        if !self.live_config.has_key_st(key) { return None; }
+        self.forget_expiry(key);
+        if let Some(journal) = &self.journal {
+            journal::append_del(journal, key).expect("Failed to append to config journal");
+        }
+        let removed = self.live_config.remove_get_key_st(key);
+        #[cfg(feature = "log")]
+        if self.change_logging && removed.is_some() {
+            changelog::record("del", key, removed.as_ref(), None, self.is_secret_st(key));
+        }
+        if removed.is_some() {
+            self.audit("del", key, removed.as_ref(), None);
+        }
+        removed
+    }
+
+    /// # Remove and Get Key (Static)
+    /// 
+    /// Remove an object's key within a value and return it if it exists
+    /// 
+    /// Will return None if used on non-objects
+    pub fn remove_get_key_st(&mut self, key: &str) -> Option<Value> {
+        let removed = self.apply_remove(key);
+        if removed.is_some() {
+            self.touch();
+            self.notify_change(&[key.to_owned()]);
+        }
+        removed
+    }
+
+    /// # Remove Key
+    /// 
+    /// Remove an object's key within a value without respect to whether its assigned, or where its value goes
+    /// 
+    /// Will do nothing if used on non-objects
+    pub fn remove_key(&mut self, key: String) {
+        self.remove_key_st(&key);
+    }
+
+    /// # Remove Key (Static)
+    /// 
+    /// Remove an object's key within a value without respect to whether its assigned, or where its value goes
+    /// 
+    /// Will do nothing if used on non-objects
+    pub fn remove_key_st(&mut self, key: &str) {
+        self.remove_get_key_st(key);
+    }
+
+    /// # Try Remove Key
+    /// 
+    /// Remove an object's key and return it if it existed, or fail with `FigConError::WrongRoot` if the config isn't rooted in an object
+    pub fn try_remove_key(&mut self, key: String) -> Result<Option<Value>, FigConError> {
+        self.try_remove_key_st(&key)
+    }
+
+    /// # Try Remove Key (Static)
+    /// 
+    /// Remove an object's key and return it if it existed, or fail with `FigConError::WrongRoot` if the config isn't rooted in an object
+    pub fn try_remove_key_st(&mut self, key: &str) -> Result<Option<Value>, FigConError> {
+        self.require_object_root()?;
+        Ok(self.remove_get_key_st(key))
+    }
+
+    /// # Drain Filter
+    /// 
+    /// Remove and return every top-level entry the predicate accepts, in one pass (like `HashMap::extract_if`)
+    /// 
+    /// Handy for removing while iterating, such as every key with some prefix or every null value.
+    /// The config is only marked dirty if something was removed. Will return nothing for non-object roots
+    pub fn drain_filter<F: FnMut(&str, &Value) -> bool>(&mut self, mut pred: F) -> Vec<(String, Value)> {
+        let keys: Vec<String> = match self.live_config.obj() {
+            Some(object) => object.iter().filter(|(key, value)| pred(key, value)).map(|(key, _)| key.clone()).collect(),
+            None => return Vec::new()
+        };
+        let drained: Vec<(String, Value)> = keys.into_iter()
+            .filter_map(|key| self.apply_remove(&key).map(|value| (key, value)))
+            .collect();
+        if !drained.is_empty() {
+            self.touch();
+            self.notify_change(&drained.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>());
+        }
+        drained
+    }
+
+    /// # Set Object
+    /// 
+    /// Overwrite an object within the value, and combine the keys inside
+    /// 
+    /// Will do nothing if used on non-objects. Panics like `set_many` (With `.with_strict_paths(true)`, or for a value nesting past `FigCon::MAX_DEPTH`)
+    pub fn set_obj(&mut self, key: String, object: Value) {
+        let Value::Object(entries) = object else { return; };
+        if entries.is_empty() || !self.live_config.is_object() { return; }
+        // Same rule as `Value::set_obj`: an existing key merges the entries into the config, otherwise the object is stored at the key
+        let entries: Vec<(String, Value)> = if self.live_config.has_key_st(&key) {
+            entries.into_iter().collect()
+        } else {
+            vec![(key, Value::Object(entries))]
+        };
+        self.set_many(entries); // journaled like every other set
+    }
+
+    /// # Set Object (Static)
+    /// 
+    /// Overwrite an object within the value, and combine the keys inside
+    /// 
+    /// Will do nothing if used on non-objects
+    pub fn set_obj_st(&mut self, key: &str, object: Value) {
+        self.set_obj(key.to_owned(), object);
+    }
+
+    /// # Get Object (Mutable)
+    /// 
+    /// Get an object within the keys
+    /// 
+    /// Will return None if used on non-objects. Edits made through the reference are not journaled
+    pub fn get_obj_mut(&mut self, key: String) -> Option<&mut Value> {
+        self.get_obj_mut_st(&key)
+    }
+
+    /// # Get Object (Mutable, Static)
+    /// 
+    /// Get an object within the keys
+    /// 
+    /// Will return None if used on non-objects. Edits made through the reference are not journaled
+    pub fn get_obj_mut_st(&mut self, key: &str) -> Option<&mut Value> {
+        self.touch();
+        self.live_config.get_obj_mut_st(key)
+    }
+
+    /// # Get Object
+    /// 
+    /// Get an object within the keys
+    /// 
+    /// Will return None if used on non-objects
+    pub fn get_obj(&self, key: String) -> Option<&Value> {
+        self.get_obj_st(&key)
     }
 
     /// # Get Object (Static)
     /// 
-    /// Get an object within the keys
+    /// Get an object within the keys
+    /// 
+    /// Will return None if used on non-objects
+    pub fn get_obj_st(&self, key: &str) -> Option<&Value> {
+        self.source(key).get_obj_st(key)
+    }
+
+    /// # New Object
+    /// 
+    /// Create a child structure within the current config with a given key
+    /// 
+    /// Panics where `try_new_obj` would fail, as there is nothing to hand back
+    pub fn new_obj(&mut self, key: String) -> &mut Value {
+        self.try_new_obj(key).expect("Refused to create config object")
+    }
+
+    /// # New Object (Static)
+    /// 
+    /// Create a child structure within the current config with a given key
+    /// 
+    /// Panics where `try_new_obj` would fail, as there is nothing to hand back
+    pub fn new_obj_st(&mut self, key: &str) -> &mut Value {
+        self.new_obj(key.to_owned())
+    }
+
+    /// # Try New Object
+    /// 
+    /// Create a child structure with a given key and borrow it, failing with `FigConError::WrongRoot` if the config isn't rooted in an object
+    /// 
+    /// The empty object is journaled, but edits made through the reference are not
+    pub fn try_new_obj(&mut self, key: String) -> Result<&mut Value, FigConError> {
+        self.try_set_key(key.clone(), json!({}))?;
+        Ok(self.live_config.get_key_mut_st(&key).expect("The key was just set"))
+    }
+
+    /// # Try New Object (Static)
+    /// 
+    /// Create a child structure with a given key and borrow it, failing with `FigConError::WrongRoot` if the config isn't rooted in an object
+    pub fn try_new_obj_st(&mut self, key: &str) -> Result<&mut Value, FigConError> {
+        self.try_new_obj(key.to_owned())
+    }
+
+    /// # Get Bytes
+    /// 
+    /// Acquire a key's value as a byte size, parsing strings like "10MB" or "512 KiB"
+    /// 
+    /// Plain numbers are treated as bytes. `KB`, `MB`, ... are decimal and `KiB`, `MiB`, ... binary (1KB = 1000 bytes, 1KiB = 1024 bytes)
+    /// 
+    /// Will return None if the key is missing or the value can't be parsed
+    pub fn get_bytes(&self, key: String) -> Option<u64> {
+        self.get_bytes_st(&key)
+    }
+
+    /// # Get Bytes (Static)
+    /// 
+    /// Acquire a key's value as a byte size, parsing strings like "10MB" or "512 KiB"
+    /// 
+    /// Plain numbers are treated as bytes. `KB`, `MB`, ... are decimal and `KiB`, `MiB`, ... binary (1KB = 1000 bytes, 1KiB = 1024 bytes)
+    /// 
+    /// Will return None if the key is missing or the value can't be parsed
+    pub fn get_bytes_st(&self, key: &str) -> Option<u64> {
+        self.source(key).get_bytes_st(key)
+    }
+
+    /// # Get Duration
+    /// 
+    /// Acquire a key's value as a duration, parsing strings like "30s", "250ms" or "2h"
+    /// 
+    /// Plain numbers are treated as seconds. Accepted units are ns, us, ms, s, m/min, h and d
+    /// 
+    /// Will return None if the key is missing or the value can't be parsed
+    pub fn get_duration(&self, key: String) -> Option<Duration> {
+        self.get_duration_st(&key)
+    }
+
+    /// # Get Duration (Static)
+    /// 
+    /// Acquire a key's value as a duration, parsing strings like "30s", "250ms" or "2h"
+    /// 
+    /// Plain numbers are treated as seconds. Accepted units are ns, us, ms, s, m/min, h and d
+    /// 
+    /// Will return None if the key is missing or the value can't be parsed
+    pub fn get_duration_st(&self, key: &str) -> Option<Duration> {
+        self.source(key).get_duration_st(key)
+    }
+
+    /// # Get F64
+    /// 
+    /// Acquire a key's value as an f64, coercing compatible values
+    /// 
+    /// Accepts any JSON number (integers included) or a string holding a finite number
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_f64(&self, key: String) -> Option<f64> {
+        self.get_f64_st(&key)
+    }
+
+    /// # Get F64 (Static)
+    /// 
+    /// Acquire a key's value as an f64, coercing compatible values
+    /// 
+    /// Accepts any JSON number (integers included) or a string holding a finite number
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_f64_st(&self, key: &str) -> Option<f64> {
+        self.source(key).get_f64_st(key)
+    }
+
+    /// # Get I64
+    /// 
+    /// Acquire a key's value as an i64, coercing compatible values
+    /// 
+    /// Accepts integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_i64(&self, key: String) -> Option<i64> {
+        self.get_i64_st(&key)
+    }
+
+    /// # Get I64 (Static)
+    /// 
+    /// Acquire a key's value as an i64, coercing compatible values
+    /// 
+    /// Accepts integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_i64_st(&self, key: &str) -> Option<i64> {
+        self.source(key).get_i64_st(key)
+    }
+
+    /// # Get U64
+    /// 
+    /// Acquire a key's value as a u64, coercing compatible values
+    /// 
+    /// Accepts non-negative integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_u64(&self, key: String) -> Option<u64> {
+        self.get_u64_st(&key)
+    }
+
+    /// # Get U64 (Static)
+    /// 
+    /// Acquire a key's value as a u64, coercing compatible values
+    /// 
+    /// Accepts non-negative integers, floats without a fractional part (`3.0`), or strings holding either, as long as the value fits.
+    /// Integers are read exactly, without passing through a float, so IDs and nanosecond timestamps survive in full
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_u64_st(&self, key: &str) -> Option<u64> {
+        self.source(key).get_u64_st(key)
+    }
+
+    /// # Get Bool
+    /// 
+    /// Acquire a key's value as a bool, coercing compatible values
+    /// 
+    /// Accepts booleans, the numbers 0 and 1, or the strings true/false, yes/no, on/off and 1/0 (case-insensitive)
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_bool(&self, key: String) -> Option<bool> {
+        self.get_bool_st(&key)
+    }
+
+    /// # Get Bool (Static)
+    /// 
+    /// Acquire a key's value as a bool, coercing compatible values
+    /// 
+    /// Accepts booleans, the numbers 0 and 1, or the strings true/false, yes/no, on/off and 1/0 (case-insensitive)
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_bool_st(&self, key: &str) -> Option<bool> {
+        self.source(key).get_bool_st(key)
+    }
+
+    /// # Get String
+    /// 
+    /// Acquire a key's value as a String, coercing compatible values
+    /// 
+    /// Accepts strings as-is, or numbers and booleans written out as text (`8080` becomes "8080")
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_string(&self, key: String) -> Option<String> {
+        self.get_string_st(&key)
+    }
+
+    /// # Get String (Static)
     /// 
-    /// Will return None if used on non-objects
-    pub fn get_obj_st(&self, key: &str) -> Option<&Value> {
-        self.get_obj(key.to_owned())
+    /// Acquire a key's value as a String, coercing compatible values
+    /// 
+    /// Accepts strings as-is, or numbers and booleans written out as text (`8080` becomes "8080")
+    /// 
+    /// Will return None if the key is missing or the value can't be coerced
+    pub fn get_string_st(&self, key: &str) -> Option<String> {
+        if self.secret_resolution && let Some(Value::String(reference)) = self.get_key_st(key) {
+            return self.resolve_secret(reference)?.as_str().map(str::to_owned);
+        }
+        self.source(key).get_string_st(key)
     }
 
-    /// # New Object
+    /// # Get Key Path
     /// 
-    /// Create a child structure within the current config with a given key
-    pub fn new_obj(&mut self, key: String) -> &mut Value {
-        self.live_config.new_obj(key).unwrap() // No option handling- Live config is always an object
+    /// Acquire a nested value by dot-path, where numeric segments index into arrays (`"servers.0.host"`)
+    /// 
+    /// Falls back to the registered defaults like `get_key`, also when the top-level key has expired (See `.set_with_ttl()`).
+    /// Returns None for missing keys and out-of-bounds indices
+    pub fn get_key_path(&self, path: &str) -> Option<&Value> {
+        let live = if self.is_expired(self.top_key(path)) { None } else { path::get_path(&self.live_config, path, self.path_separator) };
+        live.or_else(|| path::get_path(&self.defaults, path, self.path_separator))
     }
 
-    /// # New Object (Static)
+    /// # Get Path As
     /// 
-    /// Create a child structure within the current config with a given key
-    pub fn new_obj_st(&mut self, key: &str) -> &mut Value {
-        self.new_obj(key.to_owned())
+    /// Acquire a nested value by dot-path (indexing into arrays with numeric segments), deserialized into `T`
+    /// 
+    /// Returns None for missing keys, out-of-bounds indices, or values that don't fit `T`
+    pub fn get_path_as<T: DeserializeOwned>(&self, path: &str) -> Option<T> {
+        serde_json::from_value(self.get_key_path(path)?.clone()).ok()
+    }
+
+    /// # Set Key Path
+    /// 
+    /// Assign a nested value by dot-path (`"server.port"`), creating intermediate objects as needed
+    /// 
+    /// Numeric segments index into existing arrays and must be in bounds. A scalar in the way is never replaced by an object.
+    /// Will do nothing if used on non-objects, and panics where `try_set_key_path` would otherwise fail
+    /// (With `.with_strict_paths(true)`, a value nesting past `FigCon::MAX_DEPTH`, or a blocked path)
+    pub fn set_key_path(&mut self, path: &str, value: Value) {
+        if !self.live_config.is_object() { return; }
+        self.try_set_key_path(path, value).expect("Refused to set config path");
+    }
+
+    /// # Try Set Key Path
+    /// 
+    /// Assign a nested value by dot-path, returning `FigConError::WouldReplaceContainer` instead of overwriting
+    /// a whole object or array with a scalar when `.with_strict_paths(true)` is on,
+    /// `FigConError::TooDeep` when the path and value together would nest past `FigCon::MAX_DEPTH`,
+    /// `FigConError::PathBlocked` when a scalar (or an array without that index) is in the way, like `port` in `"port.number"`,
+    /// and `FigConError::WrongRoot` if the config isn't rooted in an object. Nothing is changed on error
+    pub fn try_set_key_path(&mut self, path: &str, value: Value) -> Result<(), FigConError> {
+        self.require_object_root()?;
+        check_replace(self.strict_paths, path::get_path(&self.live_config, path, self.path_separator), &value, path)?;
+        self.set_path_unchecked(path, value)
+    }
+
+    /// # Set Key Path (Force)
+    /// 
+    /// Assign a nested value by dot-path like `.set_key_path()`, replacing whatever is there even with strict paths on
+    /// 
+    /// Will do nothing if used on non-objects. Still panics when the path and value together would nest past `FigCon::MAX_DEPTH`,
+    /// or when the path is blocked (See `try_set_key_path_force`)
+    pub fn set_key_path_force(&mut self, path: &str, value: Value) {
+        if !self.live_config.is_object() { return; }
+        self.try_set_key_path_force(path, value).expect("Refused to set config path");
+    }
+
+    /// # Try Set Key Path (Force)
+    /// 
+    /// Assign a nested value by dot-path like `.try_set_key_path()`, replacing whatever is there even with strict paths on
+    /// 
+    /// Still fails with `FigConError::TooDeep`, `FigConError::PathBlocked` and `FigConError::WrongRoot`
+    pub fn try_set_key_path_force(&mut self, path: &str, value: Value) -> Result<(), FigConError> {
+        self.require_object_root()?;
+        self.set_path_unchecked(path, value)
+    }
+
+    /// # Set Path (Unchecked)
+    /// 
+    /// Assign a nested value by dot-path after the depth and path checks, logging and journaling it,
+    /// without the strict paths or root checks
+    fn set_path_unchecked(&mut self, path: &str, value: Value) -> Result<(), FigConError> {
+        check_depth(&value, path.split(self.path_separator).count())?;
+        path::check_set_path(&self.live_config, path, self.path_separator)?;
+        #[cfg(feature = "log")]
+        if self.change_logging {
+            changelog::record("set_key_path", path, path::get_path(&self.live_config, path, self.path_separator), Some(&value), self.is_secret_st(path));
+        }
+        self.audit("set_path", path, path::get_path(&self.live_config, path, self.path_separator), Some(&value));
+        path::set_path(&mut self.live_config, path, self.path_separator, value)?;
+        let top = self.top_key(path);
+        self.forget_expiry(top);
+        if let Some(journal) = &self.journal && let Some(value) = self.live_config.get_key_st(top) {
+            journal::append_set(journal, top, value).expect("Failed to append to config journal"); // journal the whole top-level key
+        }
+        self.touch();
+        self.notify_change(&[path.to_owned()]);
+        Ok(())
+    }
+
+    /// # Remove Key Path
+    /// 
+    /// Remove a nested value by dot-path (`"server.port"`) and return it if it existed, where numeric segments index into arrays
+    /// 
+    /// Removing an array element shifts the rest down. Parent objects are kept even when they end up empty
+    pub fn remove_key_path(&mut self, path: &str) -> Option<Value> {
+        if !path.contains(self.path_separator) {
+            return self.remove_get_key_st(path);
+        }
+        let removed = path::remove_path(&mut self.live_config, path, self.path_separator)?;
+        #[cfg(feature = "log")]
+        if self.change_logging {
+            changelog::record("del_path", path, Some(&removed), None, self.is_secret_st(path));
+        }
+        self.audit("del_path", path, Some(&removed), None);
+        let top = self.top_key(path);
+        self.forget_expiry(top);
+        if let Some(journal) = &self.journal && let Some(value) = self.live_config.get_key_st(top) {
+            journal::append_set(journal, top, value).expect("Failed to append to config journal"); // journal the whole top-level key
+        }
+        self.touch();
+        self.notify_change(&[path.to_owned()]);
+        Some(removed)
+    }
+
+    /// # Set If
+    /// 
+    /// Assign `value` at the dot-path `target` (like `.set_key_path()`) only when the value at the JSON Pointer `condition`
+    /// equals `expected`, returning whether the set fired
+    /// 
+    /// `set_if("/mode", &json!("advanced"), "max_threads", json!(16))` only raises the thread count in advanced mode.
+    /// The condition falls back to the registered defaults, and a condition that points at nothing never equals `expected`
+    pub fn set_if(&mut self, condition: &str, expected: &Value, target: &str, value: Value) -> bool {
+        let current = self.live_config.pointer(condition).or_else(|| self.defaults.pointer(condition));
+        if current != Some(expected) { return false; }
+        self.set_key_path(target, value);
+        true
+    }
+
+    /// # Overlay Environment
+    /// 
+    /// Override config values from environment variables starting with `prefix`, returning the dot-paths that were set
+    /// 
+    /// With the prefix `APP_`, `APP_PORT=8080` sets `port` and `APP_SERVER__HOST=example.com` sets `server.host`:
+    /// the rest of the name is lowercased and `__` separates nesting levels.
+    /// Values that parse as JSON keep their type (`8080`, `true`, `[1, 2]`), anything else is stored as a string.
+    /// Variables that would nest past `FigCon::MAX_DEPTH`, or whose path runs into a scalar (`APP_PORT__NUMBER` with a numeric `port`), are skipped
+    pub fn overlay_env(&mut self, prefix: &str) -> Vec<String> {
+        self.overlay_env_where(prefix, |_, _| true)
+    }
+
+    /// # Overlay Environment (Existing Keys)
+    /// 
+    /// Like `.overlay_env()`, but only overrides settings that already exist in the config or its registered defaults
+    /// 
+    /// Stray or typo'd variables like `APP_PROT=8080` are ignored instead of creating a bogus `prot` key
+    pub fn overlay_env_existing(&mut self, prefix: &str) -> Vec<String> {
+        self.overlay_env_where(prefix, |conf, path| conf.get_key_path(path).is_some())
+    }
+
+    /// # Overlay Environment Where
+    /// 
+    /// Apply the prefixed environment overrides that pass `keep`
+    fn overlay_env_where(&mut self, prefix: &str, keep: impl Fn(&FigCon, &str) -> bool) -> Vec<String> {
+        let mut applied = Vec::new();
+        for (_, path, value) in env::overrides(prefix, self.path_separator) {
+            if !keep(self, &path) || self.try_set_key_path_force(&path, value).is_err() { continue; }
+            applied.push(path);
+        }
+        applied
+    }
+
+    /// # Remap
+    /// 
+    /// Move values from old flat keys to new dot-paths in one pass, removing the old keys (For migrating legacy layouts)
+    /// 
+    /// `&[("server_port", "server.port")]` moves `"server_port"` into the `"server"` object.
+    /// Returns the old keys that actually existed and were moved. A key whose new path is blocked by a scalar
+    /// (or would nest past `FigCon::MAX_DEPTH`) stays where it was, untouched: nothing is journaled, audited or reported for it
+    pub fn remap(&mut self, mappings: &[(&str, &str)]) -> Vec<String> {
+        let mut fired = Vec::new();
+        for (old, new) in mappings {
+            let Some(value) = self.live_config.get_key_st(old) else { continue; };
+            // A new path running through the old key (`"server"` to `"server.main"`) is clear once the old key is removed
+            let through_old = new.split(self.path_separator).next() == Some(*old);
+            let clear = through_old || path::check_set_path(&self.live_config, new, self.path_separator).is_ok();
+            if !clear || check_depth(value, new.split(self.path_separator).count()).is_err() { continue; }
+            let value = self.remove_get_key_st(old).expect("checked above");
+            self.try_set_key_path_force(new, value).expect("checked above");
+            fired.push(old.to_string());
+        }
+        fired
+    }
+
+    /// # Namespace
+    /// 
+    /// Get a view of the config that keeps every key inside the object at `prefix`, so components can share one config without colliding
+    pub fn namespace(&mut self, prefix: &str) -> NamespacedFigCon<'_> {
+        NamespacedFigCon::new(self, prefix)
+    }
+
+    /// # Bind
+    /// 
+    /// Deserialize the whole config into a struct `T` that can be read and edited through its fields,
+    /// then written back with `.commit()` on the returned handle
+    /// 
+    /// Committing preserves config keys `T` doesn't represent. Fails with `FigConError::Typed` if the config doesn't fit `T`,
+    /// naming the dot-path of the offending value like `load_typed`
+    pub fn bind<T: Serialize + DeserializeOwned>(&mut self) -> Result<BoundFigCon<'_, T>, FigConError> {
+        BoundFigCon::new(self)
+    }
+
+    /// # Deserialize Lenient
+    /// 
+    /// Deserialize the config into a struct `T` field by field, so one corrupt setting doesn't lose all the others,
+    /// returning the struct along with the names of the fields whose config values were rejected
+    /// 
+    /// Every field starts at its value in `T::default()` (hence the `Default` bound) and takes the config's value only if
+    /// that value deserializes; rejected and missing fields keep their defaults. `T` must serialize to an object to be
+    /// taken apart like this (`Serialize` is how the defaults are read), otherwise it comes back as the whole-config result or the default
+    /// 
+    /// Fields the default doesn't serialize (`#[serde(skip_serializing)]`, or a `skip_serializing_if` that held) are still read
+    /// from the config, but they have no default to fall back on, so a rejected one is left out instead. That only works if
+    /// `T` accepts them missing (an `Option`, or `#[serde(default)]`); otherwise the default is returned whole
+    pub fn deserialize_lenient<T: Serialize + DeserializeOwned + Default>(&self) -> (T, Vec<String>) {
+        let Ok(Value::Object(mut fields)) = serde_json::to_value(T::default()) else {
+            return (T::deserialize(&self.live_config).unwrap_or_default(), Vec::new());
+        };
+        let mut keys: Vec<String> = fields.keys().cloned().collect();
+        let skipped = fields::struct_fields::<T>().unwrap_or_default().iter().filter(|field| !fields.contains_key(**field));
+        keys.extend(skipped.map(|field| (*field).to_owned()));
+        let mut failed = Vec::new();
+        for key in keys {
+            let Some(value) = self.live_config.get_key_st(&key) else { continue; };
+            let default = fields.insert(key.clone(), value.clone());
+            if T::deserialize(&fields).is_err() {
+                match default {
+                    Some(default) => fields.insert(key.clone(), default),
+                    None => fields.remove(&key)
+                };
+                failed.push(key);
+            }
+        }
+        (T::deserialize(Value::Object(fields)).unwrap_or_default(), failed)
+    }
+
+    /// # Flatten
+    /// 
+    /// List every leaf of the config as a dot-path and its value, like `("server.port", 8080)`
+    /// 
+    /// Arrays, scalars and empty objects are leaves; only non-empty objects are descended into
+    pub fn flatten(&self) -> Vec<(String, Value)> {
+        let mut leaves = Vec::new();
+        if self.any_keys() {
+            path::flatten_into("", &self.live_config, self.path_separator, &mut leaves, 0);
+        }
+        leaves
+    }
+
+    /// # Paths Of Type
+    /// 
+    /// List the dot-path of every value of the given type anywhere in the config, containers included (`Object` finds every section),
+    /// like every boolean flag or every number to stringify. Array elements are reached by index (`servers.0.port`)
+    /// 
+    /// Paths come in the config's key order, each section before what's inside it, so the result is the same from run to run.
+    /// The root itself is never listed. Feed the paths to `get_key_path` and `set_key_path` for targeted bulk edits
+    pub fn paths_of_type(&self, kind: FigConRootKind) -> Vec<String> {
+        let mut paths = Vec::new();
+        path::paths_of_kind("", &self.live_config, self.path_separator, kind, &mut paths, 0);
+        paths
+    }
+
+    /// # Infer Schema
+    /// 
+    /// Describe the config's current structure as a JSON Schema (draft 2020-12), as a skeleton to refine into a real schema:
+    /// the type of every value, the keys of every object (all marked required) and the item type of every array
+    /// 
+    /// Only what this one config shows can be inferred, so there are no ranges, patterns, enums or optional keys.
+    /// Integers become `integer` and other numbers `number`, so a float setting that happens to hold `1` comes out too strict, as does a null one.
+    /// Arrays with items of different shapes get an `anyOf` of each distinct item schema, and empty arrays allow any items
+    pub fn infer_schema(&self) -> Value {
+        let mut schema = schema::infer(&self.live_config, 0);
+        if let Value::Object(object) = &mut schema {
+            object.insert("$schema".to_owned(), Value::String(schema::DRAFT.to_owned()));
+        }
+        schema
+    }
+
+    /// # To Table
+    /// 
+    /// Render the config for a terminal as aligned `key = value (type)` rows, one per dot-path leaf like `.flatten()`
+    /// (`server.port = 8080 (number)`)
+    /// 
+    /// Arrays show their first few elements and their length, and values of keys flagged with `mark_secret` are shown as `<redacted>`.
+    /// Meant for humans, unlike the JSON of `Display`
+    pub fn to_table(&self) -> String {
+        table::render(&self.flatten(), |path| self.is_secret_st(self.top_key(path)))
+    }
+
+    /// # To Query String
+    /// 
+    /// Encode the config as `application/x-www-form-urlencoded` pairs joined with `&`
+    /// 
+    /// Nested keys are flattened into dot-paths (`{"server": {"port": 80}}` becomes `server.port=80`),
+    /// arrays become one repeated key per element, null becomes an empty value,
+    /// and empty objects or containers inside arrays are written as compact JSON
+    pub fn to_query_string(&self) -> String {
+        let mut pairs = Vec::new();
+        for (key, value) in self.flatten() {
+            let key = query::encode(&key);
+            match value {
+                Value::Array(items) => {
+                    for item in items {
+                        pairs.push(format!("{}={}", key, query::encode(&query::scalar_text(&item))));
+                    }
+                },
+                value => pairs.push(format!("{}={}", key, query::encode(&query::scalar_text(&value))))
+            }
+        }
+        pairs.join("&")
+    }
+
+    /// # From Query String
+    /// 
+    /// Build a config from `application/x-www-form-urlencoded` pairs, the reverse of `.to_query_string()`
+    /// 
+    /// Dot-path keys become nested objects and repeated keys become arrays.
+    /// Query strings carry no types, so every value comes back as a string. Keys nesting past `FigCon::MAX_DEPTH` are skipped,
+    /// and so are keys running into a value an earlier key set (`a.b` after `a=1`)
+    /// 
+    /// The resulting config has an empty path, so use `.set_path()` before saving it
+    pub fn from_query_string(query: &str) -> Self {
+        let mut grouped: Vec<(String, Vec<Value>)> = Vec::new();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let (key, value) = (query::decode(key), Value::String(query::decode(value)));
+            match grouped.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, values)) => values.push(value),
+                None => grouped.push((key, vec![value]))
+            }
+        }
+        let mut live_config = Value::Object(Map::new());
+        for (key, mut values) in grouped.into_iter().filter(|(key, _)| key.split('.').count() < MAX_DEPTH) {
+            let value = if values.len() == 1 { values.pop().unwrap() } else { Value::Array(values) };
+            let _ = path::set_path(&mut live_config, &key, '.', value); // a blocked key is skipped
+        }
+        FigCon::from_parts(live_config, PathBuf::new())
+    }
+
+    /// # Compact Memory
+    /// 
+    /// Release the spare capacity of every string and array in the config, returning how many bytes that freed
+    /// (For large, data-style configs that are kept around for a long time)
+    /// 
+    /// This is not interning: serde_json's `Value` owns every string, so identical strings can't share one allocation.
+    /// It only trims the slack left by growing strings and arrays, which is mostly in arrays built while parsing.
+    /// The values themselves don't change, so the config isn't marked dirty
+    pub fn compact_memory(&mut self) -> usize {
+        shrink_value(&mut self.live_config, 0) + shrink_value(&mut self.defaults, 0)
+    }
+
+    /// # Stats
+    /// 
+    /// Count the config's keys, nesting depth and values of each type, plus its serialized size
+    /// 
+    /// This walks the whole tree and serializes it once, so it costs about as much as a save without the IO
+    pub fn stats(&self) -> FigConStats {
+        let mut stats = FigConStats::default();
+        stats.walk(&self.live_config, 0);
+        stats.bytes = self.serialized_len().unwrap_or(0);
+        stats
+    }
+
+    /// # Diff
+    /// 
+    /// List the changes that turn this config into `other`, by dot-path
+    /// 
+    /// Objects are compared key by key, while arrays and scalars are compared as a whole
+    pub fn diff(&self, other: &FigCon) -> Vec<FigConChange> {
+        let mut changes = Vec::new();
+        diff::diff_into("", &self.live_config, &other.live_config, self.path_separator, &mut changes, 0);
+        changes
+    }
+
+    /// # Equals Ignoring
+    /// 
+    /// Compare two configs like `==`, but with the given dot-paths removed from both first
+    /// (For tests on configs carrying volatile fields like a `"last_saved"` timestamp or generated IDs)
+    /// 
+    /// Ignored paths can reach into nested objects and arrays (`"meta.generated_id"`), and paths absent from either config are fine
+    pub fn eq_ignoring(&self, other: &FigCon, ignore: &[&str]) -> bool {
+        let strip = |value: &Value| {
+            let mut value = value.clone();
+            for path in ignore {
+                path::remove_path(&mut value, path, self.path_separator);
+            }
+            value
+        };
+        strip(&self.live_config) == strip(&other.live_config)
+    }
+
+    /// # JSON Patch
+    /// 
+    /// Build an RFC 6902 JSON Patch, an array of `add`, `remove` and `replace` operations addressed by JSON Pointer,
+    /// that turns this config into `target` (The interoperable patch format many HTTP APIs accept)
+    /// 
+    /// Objects are compared key by key and arrays index by index, so an element inserted in the middle of an array
+    /// comes out as a replacement of every later element plus an `add`. The patch is correct, but not always the shortest
+    pub fn json_patch(&self, target: &FigCon) -> Value {
+        let mut ops = Vec::new();
+        jsonpatch::diff_into("", &self.live_config, &target.live_config, &mut ops, 0);
+        Value::Array(ops)
+    }
+
+    /// # Apply JSON Patch
+    /// 
+    /// Run an RFC 6902 JSON Patch (an array of `add`, `remove`, `replace`, `move`, `copy` and `test` operations)
+    /// against the config, like one sent by a remote admin API
+    /// 
+    /// The patch is all or nothing: if any operation fails, including a `test` that doesn't match,
+    /// the config is left untouched and `FigConError::PatchFailed` names the operation.
+    /// A successful patch is journaled like a transaction and reported to observers in a single `on_change`, with the top-level keys it changed
+    pub fn apply_json_patch(&mut self, patch: &Value) -> Result<(), FigConError> {
+        self.try_transaction(|txn| {
+            let mut patched = txn.working.clone();
+            jsonpatch::apply(&mut patched, patch)?;
+            check_depth(&patched, 0)?;
+            let mut keys: Vec<String> = txn.working.list_keys().unwrap_or_default();
+            keys.extend(patched.list_keys().unwrap_or_default().into_iter().filter(|key| !txn.working.has_key_st(key)));
+            txn.touched = keys.into_iter().filter(|key| txn.working.get_key_st(key) != patched.get_key_st(key)).collect();
+            txn.working = patched;
+            Ok(())
+        })
+    }
+
+    /// # Pending Changes
+    /// 
+    /// Diff what is currently saved against the live config, showing exactly what `.save()` would change (Handy for a `--dry-run`)
+    /// 
+    /// If nothing is saved yet, every key in memory is reported as `Added`
+    pub fn pending_changes(&self) -> Result<Vec<FigConChange>, FigConError> {
+        let mut saved = self.read_saved()?;
+        if let Value::Object(object) = &mut saved {
+            object.remove(EXPIRES_KEY); // expiry times aren't settings
+        }
+        let mut changes = Vec::new();
+        diff::diff_into("", &saved, &self.live_config, self.path_separator, &mut changes, 0);
+        Ok(changes)
+    }
+
+    /// # Assert Round Trip
+    /// 
+    /// Serialize the config in its format and parse it back in memory, checking that nothing was lost or altered
+    /// (Catches values a format can't represent, before they get saved and silently change)
+    /// 
+    /// The comparison is semantic: key order doesn't matter and numbers are compared by value.
+    /// With `.with_skip_nulls()`, nulls inside objects are expected to disappear, and so are expired keys
+    #[cfg(feature = "testing")]
+    pub fn assert_roundtrip(&self) -> Result<(), FigConError> {
+        let parsed = self.codec().decode(&self.to_bytes()?)?;
+        let expected = self.saved_value();
+        match roundtrip::first_mismatch("", &expected, &parsed, 0) {
+            Some(path) => Err(FigConError::RoundTrip { path }),
+            None => Ok(())
+        }
+    }
+
+    /// # Read Saved
+    /// 
+    /// Read what is currently saved at this config's path or storage, treating nothing saved as an empty config
+    fn read_saved(&self) -> Result<Value, FigConError> {
+        match &self.storage {
+            Some(storage) => Self::read_storage(storage.as_ref(), self.codec()).map(|(value, _)| value),
+            None if !self.location.exists() => Ok(Value::Object(Map::new())),
+            None => self.codec().decode(&fs::read(&self.location)?)
+        }
+    }
+
+    /// # Reserved Key Conflicts
+    /// 
+    /// List the top-level keys in the config that collide with `FigCon::RESERVED_KEYS`, so apps can warn about them
+    pub fn reserved_key_conflicts(&self) -> Vec<String> {
+        Self::RESERVED_KEYS.iter()
+            .filter(|key| self.live_config.has_key_st(key))
+            .map(|key| key.to_string())
+            .collect()
+    }
+
+    /// # Check Unknown Keys
+    /// 
+    /// List the top-level keys present in the config that the struct `T` has no field for (Catches typos like "databse")
+    /// 
+    /// Field names are compared after `#[serde(rename)]`, but aliases are not considered
+    /// 
+    /// Returns an empty list if `T` isn't a plain struct (maps, `#[serde(flatten)]`) since its fields can't be known
+    pub fn check_unknown<T: DeserializeOwned>(&self) -> Vec<String> {
+        let Some(fields) = fields::struct_fields::<T>() else { return Vec::new(); };
+        match self.list_keys() {
+            Some(keys) => keys.into_iter().filter(|key| !fields.contains(&key.as_str())).collect(),
+            None => Vec::new()
+        }
+    }
+}
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use serde_json::{Value, json};
+    use std::{fs, path::PathBuf, time::Duration};
+    use super::{DisplayFormat, FigCon, FigConError, MAX_DEPTH, ReloadOutcome};
+
+    /// A path in the temp directory that no other test uses
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("figcon-{}-{}", std::process::id(), name))
+    }
+
+    /// Arrays nested `levels` deep around a number
+    fn nested(levels: usize) -> Value {
+        (0..levels).fold(json!(0), |inner, _| Value::Array(vec![inner]))
+    }
+
+    /// Temporary files left beside a config by a save
+    fn staged_leftovers(path: &std::path::Path) -> Vec<PathBuf> {
+        let prefix = format!("{}.{}.", path.file_name().unwrap().to_str().unwrap(), std::process::id());
+        fs::read_dir(path.parent().unwrap()).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|entry| entry.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".tmp")))
+            .collect()
+    }
+
+    fn sample() -> FigCon {
+        FigCon::from_parts(json!({ "port": 8080, "server": { "host": "localhost" } }), PathBuf::new())
+    }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn preserve_order_saves_byte_identical() {
+        let path = temp_path("preserve-order.json");
+        let original = "{\n  \"zeta\": 1,\n  \"alpha\": {\n    \"mid\": true,\n    \"beta\": [\n      1,\n      2\n    ]\n  },\n  \"middle\": \"x\"\n}\n";
+        fs::write(&path, original).unwrap();
+        let conf = FigCon::try_load(path.clone()).unwrap();
+        conf.try_save().unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved, original);
+    }
+
+    #[test]
+    fn display_matches_string_helpers() {
+        let conf = sample();
+        assert_eq!(conf.to_string(), conf.to_pretty_string().unwrap()); // ToString, through Display
+        let conf = conf.with_display_format(DisplayFormat::Compact);
+        assert_eq!(conf.to_string(), conf.to_compact_string().unwrap());
+        assert_eq!(format!("{:#}", conf), conf.to_pretty_string().unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_renames_a_restricted_temp_file_into_place() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = temp_path("mode.json");
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let conf = FigCon::from_parts(json!({ "token": "secret" }), path.clone()).with_mode(0o600);
+        conf.try_save().unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        let saved = FigCon::try_load(path.clone()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode, 0o600);
+        assert_eq!(saved.live_config, json!({ "token": "secret" }));
+        assert!(staged_leftovers(&path).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_never_writes_through_a_planted_temp_file() {
+        let path = temp_path("planted.json");
+        let victim = temp_path("planted-victim");
+        fs::write(&victim, "untouched").unwrap();
+        let mut legacy = path.clone().into_os_string();
+        legacy.push(".tmp");
+        let planted = [PathBuf::from(legacy), path.with_file_name(format!("planted.json.{}.999999.tmp", std::process::id()))];
+        for link in &planted {
+            let _ = fs::remove_file(link);
+            std::os::unix::fs::symlink(&victim, link).unwrap();
+        }
+        let conf = FigCon::from_parts(json!({ "a": 1 }), path.clone());
+        conf.try_save().unwrap();
+        let opened = conf.open_staging_file(&planted[1]); // a symlink sitting at the staging name itself
+        let saved = fs::read_to_string(&path).unwrap();
+        let victim_text = fs::read_to_string(&victim).unwrap();
+        for file in &planted {
+            fs::remove_file(file).unwrap();
+        }
+        let leftovers = staged_leftovers(&path);
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&victim).unwrap();
+        assert_eq!(opened.unwrap_err().kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(victim_text, "untouched");
+        assert_eq!(saved, "{\n  \"a\": 1\n}\n");
+        assert!(leftovers.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_through_symlink_replaces_its_target() {
+        let target = temp_path("link-target.json");
+        let link = temp_path("link.json");
+        fs::write(&target, "{}").unwrap();
+        let _ = fs::remove_file(&link);
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        FigCon::from_parts(json!({ "a": 1 }), link.clone()).try_save().unwrap();
+        let refused = FigCon::from_parts(json!({ "a": 2 }), link.clone()).with_nofollow(true).try_save();
+        let is_link = fs::symlink_metadata(&link).unwrap().file_type().is_symlink();
+        let saved = FigCon::try_load(target.clone()).unwrap();
+        fs::remove_file(&link).unwrap();
+        fs::remove_file(&target).unwrap();
+        assert!(is_link);
+        assert_eq!(saved.live_config, json!({ "a": 1 }));
+        assert!(matches!(refused, Err(FigConError::Io(_))));
+    }
+
+    #[test]
+    fn set_many_checks_strict_paths_before_setting_anything() {
+        let mut conf = sample().with_strict_paths(true);
+        let result = conf.try_set_many(vec![("port".to_owned(), json!(1)), ("server".to_owned(), json!("localhost"))]);
+        assert!(matches!(result, Err(FigConError::WouldReplaceContainer { key }) if key == "server"));
+        assert_eq!(conf, sample());
+        // replacing a key set earlier in the same call checks against that entry
+        conf.try_set_many(vec![("port".to_owned(), json!({})), ("port".to_owned(), json!([]))]).unwrap();
+        assert_eq!(conf.get_key_st("port"), Some(&json!([])));
+    }
+
+    #[test]
+    #[should_panic(expected = "Refused to set config keys")]
+    fn set_obj_panics_on_strict_paths() {
+        let mut conf = FigCon::from_parts(json!({ "server": { "tls": { "on": true } } }), PathBuf::new()).with_strict_paths(true);
+        conf.set_obj_st("server", json!({ "server": "localhost" }));
+    }
+
+    #[test]
+    fn path_setters_never_replace_scalars() {
+        let mut conf = sample();
+        assert!(matches!(conf.try_set_key_path("port.number", json!(1)), Err(FigConError::PathBlocked { path }) if path == "port"));
+        assert!(conf.import_under("port.plugins", &vec![1, 2]).is_err());
+        assert!(conf.namespace("port").try_set_key_st("volume", json!(3)).is_err());
+        assert_eq!(conf, sample());
+        assert!(!conf.is_dirty());
+
+        let mut array = FigCon::from_parts(json!([1, 2]), PathBuf::new());
+        assert!(matches!(array.try_set_key_path_force("a.b", json!(1)), Err(FigConError::WrongRoot { .. })));
+        array.set_key_path("a.b", json!(1));
+        array.enable("beta");
+        assert_eq!(array.live_config, json!([1, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Refused to set config path")]
+    fn set_key_path_panics_on_blocked_path() {
+        sample().set_key_path("server.host.name", json!("x"));
+    }
+
+    #[test]
+    fn remap_keeps_keys_with_blocked_targets() {
+        let mut conf = FigCon::from_parts(json!({ "port": 8080, "old_host": "a", "server": "b" }), PathBuf::new());
+        assert!(conf.remap(&[("old_host", "server.host")]).is_empty());
+        assert!(!conf.is_dirty()); // nothing was removed and put back
+        let fired = conf.remap(&[("old_host", "server.host"), ("port", "port.number")]);
+        assert_eq!(fired, vec!["port".to_owned()]); // moving a key into itself is fine
+        assert_eq!(conf.live_config, json!({ "port": { "number": 8080 }, "old_host": "a", "server": "b" }));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn ttl_expiry_stays_out_of_the_keys() {
+        let mut conf = sample();
+        conf.set_with_ttl("token", json!("abc"), Duration::from_secs(3600));
+        assert_eq!(conf.list_keys(), Some(vec!["port".to_owned(), "server".to_owned(), "token".to_owned()]));
+        assert!(conf.reserved_key_conflicts().is_empty());
+        assert!(conf.flatten().iter().all(|(path, _)| !path.starts_with(super::EXPIRES_KEY)));
+        assert_eq!(conf.stats().keys, 4);
+    }
+
+    #[test]
+    fn expired_keys_read_as_missing_and_are_not_saved() {
+        let mut conf = sample();
+        conf.set_with_ttl("server", json!({ "host": "cached" }), Duration::ZERO);
+        assert_eq!(conf.get_key_st("server"), None);
+        assert_eq!(conf.get_key_path("server.host"), None);
+        assert!(!conf.has_key_st("server"));
+        assert_eq!(conf.saved_value().into_owned(), json!({ "port": 8080 }));
+        assert_eq!(conf.purge_expired(), vec!["server".to_owned()]);
+        assert_eq!(conf.live_config, json!({ "port": 8080 }));
+    }
+
+    #[test]
+    fn other_setters_make_ttl_keys_permanent() {
+        let mut conf = sample();
+        conf.set_with_ttl("a", json!(1), Duration::ZERO);
+        conf.set_with_ttl("b", json!(1), Duration::ZERO);
+        conf.set_with_ttl("c", json!({}), Duration::ZERO);
+        conf.set_with_ttl("d", json!(1), Duration::ZERO);
+        conf.set_many(vec![("a".to_owned(), json!(2))]);
+        conf.merge(json!({ "b": 2 }), super::ArrayMergeStrategy::Replace);
+        conf.set_key_path("c.x", json!(2));
+        conf.transaction(|txn| -> Result<(), FigConError> { txn.set_key_st("d", json!(2)); Ok(()) }).unwrap();
+        assert!(conf.expires.is_empty());
+        assert_eq!(conf.get_key_st("a"), Some(&json!(2)));
+        assert_eq!(conf.get_key_path("c.x"), Some(&json!(2)));
+        assert!(conf.purge_expired().is_empty());
+    }
+
+    #[test]
+    fn ttl_survives_save_and_reload() {
+        let path = temp_path("ttl.json");
+        let mut conf = FigCon::from_parts(json!({ "port": 8080 }), path.clone());
+        conf.set_with_ttl("token", json!("abc"), Duration::from_secs(3600));
+        conf.set_with_ttl("stale", json!("old"), Duration::ZERO);
+        conf.try_save().unwrap();
+        let saved: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let reloaded = conf.reload();
+        fs::remove_file(&path).unwrap();
+        assert!(saved.get("__expires").is_some_and(|expires| expires.get("token").is_some() && expires.get("stale").is_none()));
+        assert!(saved.get("stale").is_none());
+        assert_eq!(reloaded.live_config, json!({ "port": 8080, "token": "abc" }));
+        assert_eq!(reloaded.expires.keys().collect::<Vec<_>>(), vec!["token"]);
+    }
+
+    #[test]
+    fn ttl_is_replayed_from_the_journal() {
+        let journal = temp_path("ttl-journal.jsonl");
+        let _ = fs::remove_file(&journal);
+        let mut conf = sample().with_journal(journal.clone());
+        conf.set_with_ttl("token", json!("abc"), Duration::from_secs(3600));
+        conf.set_with_ttl("other", json!(1), Duration::from_secs(3600));
+        conf.set_key_st("other", json!(2));
+        let replayed = sample().with_journal(journal.clone());
+        fs::remove_file(&journal).unwrap();
+        assert_eq!(replayed.get_key_st("token"), Some(&json!("abc")));
+        assert_eq!(replayed.expires.keys().collect::<Vec<_>>(), vec!["token"]);
+        assert!(replayed.live_config.get(super::EXPIRES_KEY).is_none());
+    }
+
+    #[test]
+    fn saving_to_a_directory_is_reported() {
+        let dir = temp_path("save-dir");
+        fs::create_dir_all(&dir).unwrap();
+        let result = FigCon::from_parts(json!({}), dir.clone()).try_save();
+        fs::remove_dir(&dir).unwrap();
+        assert!(matches!(result, Err(FigConError::PathIsDirectory { path }) if path == dir));
+    }
+
+    #[test]
+    fn saving_under_a_file_is_reported() {
+        let file = temp_path("save-parent-file");
+        fs::write(&file, "").unwrap();
+        let result = FigCon::from_parts(json!({}), file.join("config.json")).try_save();
+        fs::remove_file(&file).unwrap();
+        assert!(matches!(result, Err(FigConError::ParentNotDirectory { path }) if path == file));
+    }
+
+    #[test]
+    fn load_first_existing_returns_parse_errors() {
+        let (missing, broken, valid) = (temp_path("first-missing.json"), temp_path("first-broken.json"), temp_path("first-valid.json"));
+        fs::write(&broken, "{ nope").unwrap();
+        fs::write(&valid, "{\"a\": 1}").unwrap();
+        let picked = FigCon::load_first_existing(&[missing.clone(), valid.clone(), broken.clone()]).unwrap();
+        let failed = FigCon::load_first_existing(&[missing.clone(), broken.clone(), valid.clone()]);
+        let fresh = FigCon::load_first_existing(std::slice::from_ref(&missing)).unwrap();
+        fs::remove_file(&broken).unwrap();
+        fs::remove_file(&valid).unwrap();
+        assert_eq!((picked.path(), &picked.live_config), (valid.as_path(), &json!({ "a": 1 })));
+        assert!(matches!(failed, Err(FigConError::Parse { .. })));
+        assert_eq!(fresh.path(), missing.as_path());
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn try_load_picks_the_format_by_extension() {
+        let path = temp_path("try-load.cbor");
+        FigCon::from_parts(json!({ "a": 1 }), path.clone()).with_format(super::Format::Cbor).try_save().unwrap();
+        let loaded = FigCon::try_load(path.clone()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.live_config, json!({ "a": 1 }));
+        assert_eq!(loaded.format, super::Format::Cbor);
+    }
+
+    #[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Lenient {
+        port: u16,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        host: Option<String>,
+        #[serde(default, skip_serializing)]
+        retries: u8
+    }
+
+    #[test]
+    fn lenient_reads_fields_the_default_skips() {
+        let conf = FigCon::from_parts(json!({ "port": 9000, "host": "example.org", "retries": 3 }), PathBuf::new());
+        let (typed, failed) = conf.deserialize_lenient::<Lenient>();
+        assert_eq!(typed, Lenient { port: 9000, host: Some("example.org".into()), retries: 3 });
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn bind_reports_type_mismatches_as_typed() {
+        let mut conf = FigCon::from_parts(json!({ "port": "nope" }), PathBuf::new());
+        let bound = conf.bind::<Lenient>().map(|_| ());
+        assert!(matches!(bound, Err(FigConError::Typed { path, .. }) if path == "port"));
+    }
+
+    #[test]
+    fn lenient_drops_rejected_skipped_fields() {
+        let conf = FigCon::from_parts(json!({ "port": "nope", "host": 7, "retries": 3 }), PathBuf::new());
+        let (typed, failed) = conf.deserialize_lenient::<Lenient>();
+        assert_eq!(typed, Lenient { port: 0, host: None, retries: 3 });
+        assert_eq!(failed, ["port", "host"]);
+    }
+
+    #[test]
+    fn load_auto_rejects_toml_and_yaml() {
+        let path = temp_path("auto.toml");
+        fs::write(&path, "{\"port\": 8080}").unwrap(); // would parse as JSON, but the extension says otherwise
+        let loaded = FigCon::load_auto(path.clone());
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(loaded, Err(FigConError::UnsupportedFormat { extension }) if extension == "toml"));
+        let missing = FigCon::load_auto(temp_path("auto-missing.YML"));
+        assert!(matches!(missing, Err(FigConError::UnsupportedFormat { extension }) if extension == "YML"));
+    }
+
+    /// A toy codec for flat `key = integer` lines, standing in for a TOML backend
+    struct FlatLines;
+
+    impl super::Codec for FlatLines {
+        fn encode(&self, value: &Value) -> Result<Vec<u8>, FigConError> {
+            let object = value.as_object().ok_or_else(|| FigConError::Codec("not an object".to_owned()))?;
+            Ok(object.iter().map(|(key, value)| format!("{} = {}\n", key, value)).collect::<String>().into_bytes())
+        }
+
+        fn decode(&self, bytes: &[u8]) -> Result<Value, FigConError> {
+            let text = std::str::from_utf8(bytes).map_err(|err| FigConError::Codec(err.to_string()))?;
+            text.lines().map(|line| {
+                let (key, value) = line.split_once(" = ").ok_or_else(|| FigConError::Codec(format!("bad line {:?}", line)))?;
+                let value: i64 = value.parse().map_err(|_| FigConError::Codec(format!("bad number {:?}", value)))?;
+                Ok((key.to_owned(), json!(value)))
+            }).collect::<Result<serde_json::Map<_, _>, _>>().map(Value::Object)
+        }
+    }
+
+    #[test]
+    fn toml_files_need_a_codec() {
+        let path = temp_path("codec.toml");
+        fs::write(&path, "port = 8080\nworkers = 4\n").unwrap();
+        assert!(matches!(FigCon::try_load(path.clone()), Err(FigConError::UnsupportedFormat { .. })));
+        let mut conf = FigCon::load_with_codec(path.clone(), Box::new(FlatLines)).unwrap();
+        assert_eq!(conf.get_key_st("port"), Some(&json!(8080)));
+        conf.set_key_st("workers", json!(8));
+        conf.try_save().unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved, "port = 8080\nworkers = 8\n");
+    }
+
+    #[test]
+    fn reload_or_keep_skips_unchanged_bytes() {
+        let path = temp_path("reload-unchanged.json");
+        fs::write(&path, "{\"port\": 8080}").unwrap();
+        let mut conf = FigCon::try_load(path.clone()).unwrap();
+        conf.set_key_st("port", json!(9090)); // unsaved, so a real reload would drop it
+        fs::write(&path, "{\"port\": 8080}").unwrap(); // touched, same bytes
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Unchanged);
+        assert_eq!(conf.get_key_st("port"), Some(&json!(9090)));
+        fs::write(&path, "{\"port\": 7070}").unwrap();
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Reloaded);
+        assert_eq!(conf.get_key_st("port"), Some(&json!(7070)));
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Unchanged);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::FileMissing);
+    }
+
+    #[test]
+    fn merge_with_keeps_the_larger_number() {
+        let mut conf = FigCon::from_parts(json!({ "limits": { "rps": 100, "burst": 10 }, "name": "a" }), PathBuf::new());
+        let other = FigCon::from_parts(json!({ "limits": { "rps": 50, "burst": 20, "queue": 5 }, "name": "b" }), PathBuf::new());
+        let mut asked = Vec::new();
+        conf.merge_with(&other, |path, current, incoming| {
+            asked.push(path.to_owned());
+            match (current.as_f64(), incoming.as_f64()) {
+                (Some(left), Some(right)) if right > left => incoming.clone(),
+                _ => current.clone()
+            }
+        });
+        asked.sort();
+        assert_eq!(asked, ["limits.burst", "limits.rps", "name"]); // limits.queue is only in the other config
+        assert_eq!(conf.get_key_st("limits"), Some(&json!({ "rps": 100, "burst": 20, "queue": 5 })));
+        assert_eq!(conf.get_key_st("name"), Some(&json!("a")));
+    }
+
+    #[test]
+    fn extreme_integers_survive_save_and_reload() {
+        for format in super::Format::ENABLED.iter().copied() {
+            let mut conf = FigCon::with_storage(Box::new(super::MemoryStorage::new()), format);
+            conf.set_i64_st("max", i64::MAX);
+            conf.set_i64_st("min", i64::MIN);
+            conf.set_u64_st("unsigned", u64::MAX);
+            conf.try_save().unwrap();
+            let reloaded = conf.reload();
+            assert_eq!(reloaded.get_i64_st("max"), Some(i64::MAX), "{}", format);
+            assert_eq!(reloaded.get_i64_st("min"), Some(i64::MIN), "{}", format);
+            assert_eq!(reloaded.get_u64_st("unsigned"), Some(u64::MAX), "{}", format);
+            assert_eq!(reloaded.get_key_st("unsigned"), Some(&json!(u64::MAX)), "{}", format);
+        }
+    }
+
+    #[test]
+    fn cloned_config_doesnt_repeat_audit_lines() {
+        let path = temp_path("audited.json");
+        let audit = temp_path("audited.log");
+        let _ = fs::remove_file(&audit);
+        let mut conf = FigCon::from_parts(json!({}), path.clone()).with_audit_log(audit.clone());
+        conf.set_key_st("a", json!(1));
+        let copy = conf.clone();
+        copy.try_save().unwrap();
+        conf.try_save().unwrap();
+        conf.set_key_st("b", json!(2));
+        let mut reloaded = conf.reload(); // the pending line for "b" moves over
+        conf.try_save().unwrap();
+        reloaded.set_key_st("b", json!(2));
+        reloaded.try_save().unwrap();
+        let lines = fs::read_to_string(&audit).unwrap();
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&audit).unwrap();
+        let keys: Vec<Value> = lines.lines().map(|line| serde_json::from_str::<Value>(line).unwrap()["key"].clone()).collect();
+        assert_eq!(keys, [json!("a"), json!("b"), json!("b")]);
+    }
+
+    #[test]
+    fn reload_or_keep_throttles_to_the_interval() {
+        let path = temp_path("reload-throttled.json");
+        fs::write(&path, "{\"port\": 1}").unwrap();
+        let mut conf = FigCon::try_load(path.clone()).unwrap().with_reload_interval(Duration::from_secs(3600));
+        fs::write(&path, "{\"port\": 2}").unwrap();
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Reloaded);
+        fs::write(&path, "{\"port\": 3}").unwrap();
+        fs::write(&path, "{\"port\": 4}").unwrap();
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Throttled);
+        assert_eq!(conf.get_key_st("port"), Some(&json!(2)));
+        let mut conf = conf.with_reload_interval(Duration::ZERO); // the interval has passed
+        assert_eq!(conf.reload_or_keep(), ReloadOutcome::Reloaded);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(conf.get_key_st("port"), Some(&json!(4)));
+    }
+
+    #[test]
+    fn every_loader_remembers_the_loaded_bytes() {
+        let path = temp_path("loaded-hash.json");
+        fs::write(&path, "{\"port\": 8080}").unwrap();
+        let loaders = [
+            FigCon::load_auto(path.clone()).unwrap(),
+            FigCon::try_load_lenient(path.clone()).unwrap(),
+            FigCon::load_or_default(path.clone()),
+            FigCon::load_with_format(path.clone(), super::Format::Json)
+        ];
+        for mut conf in loaders {
+            conf.set_key_st("port", json!(1)); // kept, since the file is as loaded
+            assert_eq!(conf.reload_or_keep(), ReloadOutcome::Unchanged);
+            assert_eq!(conf.get_key_st("port"), Some(&json!(1)));
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn toggle_reports_values_it_cant_store() {
+        let mut conf = FigCon::from_parts(json!({ "dark": "off", "panel": { "open": true } }), PathBuf::new()).with_strict_paths(true);
+        assert!(conf.toggle_st("dark").unwrap());
+        assert!(!conf.toggle_st("dark").unwrap());
+        assert!(matches!(conf.toggle_st("panel"), Err(FigConError::WouldReplaceContainer { .. })));
+        assert_eq!(conf.get_key_st("panel"), Some(&json!({ "open": true })));
+        let mut array = FigCon::from_parts(json!([true]), PathBuf::new());
+        assert!(matches!(array.toggle_st("dark"), Err(FigConError::WrongRoot { .. })));
+    }
+
+    #[test]
+    fn transaction_commits_path_edits() {
+        let mut conf = sample();
+        conf.try_transaction(|txn| -> Result<(), FigConError> {
+            txn.try_set_key_path("server.port", json!(9090))?;
+            txn.remove_key_path("server.host");
+            txn.try_set_key_st("debug", json!(true))
+        }).unwrap();
+        assert_eq!(conf.live_config, json!({ "port": 8080, "server": { "port": 9090 }, "debug": true }));
+        assert!(conf.is_dirty());
+    }
+
+    #[test]
+    fn transaction_rejects_too_deep_values_before_commit() {
+        let mut conf = sample();
+        let result = conf.try_transaction(|txn| {
+            txn.try_set_key_st("port", json!(1))?;
+            txn.try_set_key_st("deep", nested(MAX_DEPTH))
+        });
+        assert!(matches!(result, Err(FigConError::TooDeep { .. })));
+        assert_eq!(conf, sample());
+        assert!(!conf.is_dirty());
+    }
+
+    #[test]
+    fn transaction_removing_nothing_changes_nothing() {
+        let mut conf = sample();
+        conf.transaction(|txn| -> Result<(), FigConError> {
+            assert_eq!(txn.remove_get_key_st("missing"), None);
+            txn.remove_key_st("also_missing");
+            Ok(())
+        }).unwrap();
+        assert!(!conf.is_dirty());
+    }
+
+    #[test]
+    fn transaction_honours_strict_paths() {
+        let mut conf = sample().with_strict_paths(true);
+        let by_key = conf.try_transaction(|txn| txn.try_set_key_st("server", json!("flat")));
+        let by_path = conf.try_transaction(|txn| txn.try_set_key_path("server", json!(1)));
+        assert!(matches!(by_key, Err(FigConError::WouldReplaceContainer { key }) if key == "server"));
+        assert!(matches!(by_path, Err(FigConError::WouldReplaceContainer { key }) if key == "server"));
+        assert_eq!(conf, sample());
+    }
+
+    #[test]
+    fn transaction_returns_journal_failures() {
+        let journal = temp_path("txn-journal-dir");
+        fs::create_dir_all(&journal).unwrap();
+        let mut conf = sample();
+        conf.journal = Some(journal.clone()); // a directory can't be appended to
+        let result = conf.try_transaction(|txn| txn.try_set_key_st("port", json!(9090)));
+        fs::remove_dir(&journal).unwrap();
+        assert!(matches!(result, Err(FigConError::Io(_))));
+        assert_eq!(conf, sample());
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(feature = "std")]
+use alloc::format;
+use serde_json::{Map, Value};
+use crate::MAX_DEPTH;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+/// # Array Merge Strategy
+///
+/// How `.merge()` and `.patch()` combine an array in the config with an array in the overlay
+pub enum ArrayMergeStrategy {
+    /// The overlay's array replaces the config's array wholesale
+    #[default]
+    Replace,
+    /// The overlay's elements are appended after the config's elements
+    Concat,
+    /// Overlapping indices are deep-merged, and the extra elements of the longer array are kept
+    ByIndex
+}
+
+/// # Merge Into
+///
+/// Deep-merge `overlay` into `target`: objects are combined key by key, arrays follow `strategy`,
+/// and anything else is replaced by the overlay
+///
+/// With `remove_nulls`, a null in an overlay object deletes that key instead of being stored (JSON Merge Patch)
+///
+/// `depth` is how many containers deep `target` sits; from `MAX_DEPTH` down the overlay replaces it wholesale
+pub(crate) fn merge_into(target: &mut Value, overlay: Value, strategy: ArrayMergeStrategy, remove_nulls: bool, depth: usize) {
+    if depth >= MAX_DEPTH {
+        *target = overlay;
+        return;
+    }
+    match (target, overlay) {
+        (Value::Object(target_object), Value::Object(overlay_object)) => {
+            for (key, value) in overlay_object {
+                if remove_nulls && value.is_null() {
+                    target_object.remove(&key);
+                } else {
+                    merge_into(target_object.entry(key).or_insert(Value::Null), value, strategy, remove_nulls, depth + 1);
+                }
+            }
+        }
+        (Value::Array(target_items), Value::Array(overlay_items)) if strategy != ArrayMergeStrategy::Replace => {
+            if strategy == ArrayMergeStrategy::Concat {
+                target_items.extend(overlay_items);
+                return;
+            }
+            for (index, item) in overlay_items.into_iter().enumerate() {
+                match target_items.get_mut(index) {
+                    Some(existing) => merge_into(existing, item, strategy, remove_nulls, depth + 1),
+                    None => target_items.push(item)
+                }
+            }
+        }
+        (target, Value::Object(overlay_object)) => {
+            // Merge into an empty object rather than copying, so patches still drop their nulls
+            *target = Value::Object(Map::new());
+            merge_into(target, Value::Object(overlay_object), strategy, remove_nulls, depth);
+        }
+        (target, overlay) => *target = overlay
+    }
+}
+
+/// # Resolve Into
+///
+/// Deep-merge `incoming` into `target` like `merge_into`, but let `resolver` pick the value wherever the two differ
+/// and aren't both objects. `path` is where `target` sits, with `sep` between the keys, `depth` containers deep;
+/// objects from `MAX_DEPTH` down are handed to the resolver whole
+#[cfg(feature = "std")]
+pub(crate) fn resolve_into<F: FnMut(&str, &Value, &Value) -> Value>(target: &mut Value, incoming: &Value, path: &str, sep: char, resolver: &mut F, depth: usize) {
+    match (target, incoming) {
+        (Value::Object(target_object), Value::Object(incoming_object)) if depth < MAX_DEPTH => {
+            for (key, value) in incoming_object {
+                match target_object.get_mut(key) {
+                    Some(existing) => resolve_into(existing, value, &format!("{}{}{}", path, sep, key), sep, resolver, depth + 1),
+                    None => { target_object.insert(key.clone(), value.clone()); }
+                }
+            }
+        }
+        (target, incoming) if target != incoming => *target = resolver(path, target, incoming),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use super::{ArrayMergeStrategy, merge_into};
+
+    fn merged(target: Value, overlay: Value, strategy: ArrayMergeStrategy) -> Value {
+        let mut target = target;
+        merge_into(&mut target, overlay, strategy, false, 0);
+        target
+    }
+
+    #[test]
+    fn replace_swaps_arrays_wholesale() {
+        let result = merged(json!({ "hosts": ["a", "b", "c"] }), json!({ "hosts": ["d"] }), ArrayMergeStrategy::Replace);
+        assert_eq!(result, json!({ "hosts": ["d"] }));
+    }
+
+    #[test]
+    fn objects_merge_deeply() {
+        let target = json!({ "server": { "host": "localhost", "tls": { "on": false, "port": 443 } }, "debug": true });
+        let overlay = json!({ "server": { "tls": { "on": true } }, "name": "figcon" });
+        let result = merged(target, overlay, ArrayMergeStrategy::Replace);
+        assert_eq!(result, json!({
+            "server": { "host": "localhost", "tls": { "on": true, "port": 443 } },
+            "debug": true,
+            "name": "figcon"
+        }));
+    }
+
+    #[test]
+    fn concat_appends_overlay_elements() {
+        let result = merged(json!({ "hosts": ["a", "b"] }), json!({ "hosts": ["b", "c"] }), ArrayMergeStrategy::Concat);
+        assert_eq!(result, json!({ "hosts": ["a", "b", "b", "c"] }));
+    }
+
+    #[test]
+    fn by_index_merges_overlap_and_keeps_extras() {
+        let target = json!({ "pools": [{ "size": 1, "name": "a" }, { "size": 2 }, { "size": 3 }] });
+        let overlay = json!({ "pools": [{ "size": 10 }, 20] });
+        let result = merged(target, overlay, ArrayMergeStrategy::ByIndex);
+        assert_eq!(result, json!({ "pools": [{ "size": 10, "name": "a" }, 20, { "size": 3 }] }));
+
+        let longer = merged(json!([1]), json!([5, 6, 7]), ArrayMergeStrategy::ByIndex);
+        assert_eq!(longer, json!([5, 6, 7]));
+    }
+
+    #[test]
+    fn remove_nulls_deletes_keys() {
+        let mut target = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        merge_into(&mut target, json!({ "a": null, "b": { "d": null } }), ArrayMergeStrategy::Replace, true, 0);
+        assert_eq!(target, json!({ "b": { "c": 2 } }));
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    fs::File,
+    io,
+    ops::Deref,
+    path::Path
+};
+
+/// # Mapping
+///
+/// A file's bytes, memory-mapped read-only where the platform supports it and read normally elsewhere
+///
+/// The mapping is undefined behavior if the file is truncated or rewritten while it is alive,
+/// which is why it is only ever held for the duration of a single parse
+pub(crate) enum Mapping {
+    #[cfg(unix)]
+    Mapped { ptr: *mut libc::c_void, len: usize },
+    Read(Vec<u8>)
+}
+
+impl Mapping {
+    /// # Open
+    ///
+    /// Map a whole file into memory (Empty files can't be mapped and come back as an empty buffer)
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len()).map_err(|_| io::Error::from(io::ErrorKind::OutOfMemory))?;
+        if len == 0 {
+            return Ok(Mapping::Read(Vec::new()));
+        }
+        Self::map(&file, len)
+    }
+
+    #[cfg(unix)]
+    fn map(file: &File, len: usize) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+        // SAFETY: a fresh private read-only mapping of an open file, checked for failure before use
+        let ptr = unsafe { libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mapping::Mapped { ptr, len })
+    }
+
+    #[cfg(not(unix))]
+    fn map(file: &File, len: usize) -> io::Result<Self> {
+        use std::io::Read;
+        let mut buffer = Vec::with_capacity(len);
+        (&*file).read_to_end(&mut buffer)?;
+        Ok(Mapping::Read(buffer))
+    }
+}
+
+impl Deref for Mapping {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            // SAFETY: the mapping is `len` readable bytes until it is dropped
+            #[cfg(unix)]
+            Mapping::Mapped { ptr, len } => unsafe { std::slice::from_raw_parts(*ptr as *const u8, *len) },
+            Mapping::Read(buffer) => buffer
+        }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Mapping::Mapped { ptr, len } = self {
+            // SAFETY: unmapping exactly what `map` mapped, once
+            unsafe { libc::munmap(*ptr, *len); }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::{FigCon, FigConError, path};
+
+#[derive(Debug)]
+/// # Namespaced FigCon
+/// 
+/// A view of a FigCon that keeps every key inside the object at `prefix`, from `FigCon::namespace()`
+/// 
+/// Lets a library store its settings in a host app's config without colliding: with the prefix `myplugin`,
+/// the key `volume` is stored in the `myplugin` object (created on the first set), so the host reads it as
+/// `get_key_path("myplugin.volume")` and can move or remove the whole namespace as one value.
+/// The prefix may itself be a path, and both follow the parent's path separator. Every change goes through the parent config,
+/// so it is marked dirty (and journaled or logged) exactly as if it was edited directly
+pub struct NamespacedFigCon<'a> {
+    conf: &'a mut FigCon,
+    prefix: String
+}
+
+impl<'a> NamespacedFigCon<'a> {
+    pub(crate) fn new(conf: &'a mut FigCon, prefix: &str) -> Self {
+        NamespacedFigCon { conf, prefix: prefix.to_owned() }
+    }
+
+    /// # Full Path
+    /// 
+    /// The path of a key in the parent config
+    fn full_path(&self, key: &str) -> String {
+        format!("{}{}{}", self.prefix, self.conf.path_separator, key)
+    }
+
+    /// # Get Key
+    /// 
+    /// Acquire a key's value within this namespace
+    pub fn get_key(&self, key: String) -> Option<&Value> {
+        self.conf.get_key_path(&self.full_path(&key))
+    }
+
+    /// # Get Key (Static)
+    /// 
+    /// Acquire a key's value within this namespace
+    pub fn get_key_st(&self, key: &str) -> Option<&Value> {
+        self.get_key(key.to_owned())
+    }
+
+    /// # Get Key As
+    /// 
+    /// Acquire a key's value within this namespace, deserialized into `T`
+    pub fn get_as<T: DeserializeOwned>(&self, key: String) -> Option<T> {
+        self.conf.get_path_as(&self.full_path(&key))
+    }
+
+    /// # Get Key As (Static)
+    /// 
+    /// Acquire a key's value within this namespace, deserialized into `T`
+    pub fn get_as_st<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get_as(key.to_owned())
+    }
+
+    /// # Set Key
+    /// 
+    /// Assign a key's value within this namespace
+    /// 
+    /// Panics like `FigCon::set_key_path()`, such as when the namespace itself holds a scalar (See `try_set_key`)
+    pub fn set_key(&mut self, key: String, value: Value) {
+        let path = self.full_path(&key);
+        self.conf.set_key_path(&path, value);
+    }
+
+    /// # Set Key (Static)
+    /// 
+    /// Assign a key's value within this namespace
+    pub fn set_key_st(&mut self, key: &str, value: Value) {
+        self.set_key(key.to_owned(), value);
+    }
+
+    /// # Try Set Key
+    /// 
+    /// Assign a key's value within this namespace, failing like `FigCon::try_set_key_path()`
+    /// (`FigConError::PathBlocked` when the namespace holds a scalar) and leaving the config untouched
+    pub fn try_set_key(&mut self, key: String, value: Value) -> Result<(), FigConError> {
+        let path = self.full_path(&key);
+        self.conf.try_set_key_path(&path, value)
+    }
+
+    /// # Try Set Key (Static)
+    /// 
+    /// Assign a key's value within this namespace, failing like `try_set_key`
+    pub fn try_set_key_st(&mut self, key: &str, value: Value) -> Result<(), FigConError> {
+        self.try_set_key(key.to_owned(), value)
+    }
+
+    /// # Has Key
+    /// 
+    /// Check if a key exists within this namespace
+    pub fn has_key(&self, key: String) -> bool {
+        path::get_path(&self.conf.live_config, &self.full_path(&key), self.conf.path_separator).is_some()
+    }
+
+    /// # Has Key (Static)
+    /// 
+    /// Check if a key exists within this namespace
+    pub fn has_key_st(&self, key: &str) -> bool {
+        self.has_key(key.to_owned())
+    }
+
+    /// # Remove and Get Key
+    /// 
+    /// Remove a key within this namespace and return its value if it existed
+    pub fn remove_get_key(&mut self, key: String) -> Option<Value> {
+        let path = self.full_path(&key);
+        self.conf.remove_key_path(&path)
+    }
+
+    /// # Remove and Get Key (Static)
+    /// 
+    /// Remove a key within this namespace and return its value if it existed
+    pub fn remove_get_key_st(&mut self, key: &str) -> Option<Value> {
+        self.remove_get_key(key.to_owned())
+    }
+
+    /// # Remove Key
+    /// 
+    /// Remove a key within this namespace
+    pub fn remove_key(&mut self, key: String) {
+        self.remove_get_key(key);
+    }
+
+    /// # Remove Key (Static)
+    /// 
+    /// Remove a key within this namespace
+    pub fn remove_key_st(&mut self, key: &str) {
+        self.remove_key(key.to_owned());
+    }
+}
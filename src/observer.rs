@@ -0,0 +1,34 @@
+use std::path::Path;
+use crate::FigConError;
+
+/// # FigCon Observer
+/// 
+/// Lifecycle hooks for logging or measuring config activity, registered with `FigCon::with_observer()`
+/// 
+/// Every hook does nothing by default, so only the interesting ones need implementing
+pub trait FigConObserver: Send + Sync {
+    /// # On Load
+    /// 
+    /// Called after the config file was read again with `.reload()`
+    /// 
+    /// The initial load happens before an observer can be attached, so it is never reported
+    fn on_load(&self, _path: &Path) {}
+
+    /// # On Save
+    /// 
+    /// Called after the config was written, with the number of bytes written
+    fn on_save(&self, _path: &Path, _bytes: usize) {}
+
+    /// # On Change
+    /// 
+    /// Called after keys changed through `set_key`, `remove_key`, `set_obj`, `set_key_path`, `set_many`, `drain_filter`, `merge`, `patch`, `apply_json_patch` or a transaction,
+    /// with every key (or dot-path) affected. Batched edits report all their keys in a single call
+    /// 
+    /// Edits made through mutable references (`get_key_mut`, `new_obj`, ...) can't be seen and are never reported
+    fn on_change(&self, _keys: &[String]) {}
+
+    /// # On Error
+    /// 
+    /// Called whenever saving fails, right before the error is returned (or `.save()` panics)
+    fn on_error(&self, _err: &FigConError) {}
+}
@@ -0,0 +1,188 @@
+use serde_json::{Map, Value};
+use crate::{FigConError, FigConRootKind, MAX_DEPTH};
+
+/// # Flatten Into
+///
+/// Walk a value and push every leaf as a (path, value) pair, with `sep` between the keys
+///
+/// Arrays, scalars and empty objects count as leaves; only non-empty objects are descended into,
+/// and only above `MAX_DEPTH` (`depth` is how many containers deep `value` sits)
+pub(crate) fn flatten_into(prefix: &str, value: &Value, sep: char, out: &mut Vec<(String, Value)>, depth: usize) {
+    match value {
+        Value::Object(object) if !object.is_empty() && depth < MAX_DEPTH => {
+            for (key, child) in object {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}{}{}", prefix, sep, key) };
+                flatten_into(&path, child, sep, out, depth + 1);
+            }
+        },
+        _ => out.push((prefix.to_owned(), value.clone()))
+    }
+}
+
+/// # Paths Of Kind
+///
+/// Walk a value and push the path (with `sep` between the keys) of everything below it that is of `kind`,
+/// containers included, each before the values inside it. Array elements are reached by index (`servers.0`)
+///
+/// `depth` is how many containers deep `value` sits, and nothing from `MAX_DEPTH` down is visited
+pub(crate) fn paths_of_kind(prefix: &str, value: &Value, sep: char, kind: FigConRootKind, out: &mut Vec<String>, depth: usize) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let mut visit = |path: String, child: &Value| {
+        if FigConRootKind::of(child) == kind {
+            out.push(path.clone());
+        }
+        paths_of_kind(&path, child, sep, kind, out, depth + 1);
+    };
+    let join = |key: &str| if prefix.is_empty() { key.to_owned() } else { format!("{}{}{}", prefix, sep, key) };
+    match value {
+        Value::Object(object) => object.iter().for_each(|(key, child)| visit(join(key), child)),
+        Value::Array(items) => items.iter().enumerate().for_each(|(index, child)| visit(join(&index.to_string()), child)),
+        _ => {}
+    }
+}
+
+/// # Get Path
+///
+/// Follow a path of keys separated by `sep` through objects and arrays, where numeric segments index into arrays (`servers.0.host`)
+///
+/// Returns None as soon as a segment is missing, an index is out of bounds, or a scalar is reached early
+pub(crate) fn get_path<'a>(root: &'a Value, path: &str, sep: char) -> Option<&'a Value> {
+    path.split(sep).try_fold(root, |current, segment| match current {
+        Value::Object(object) => object.get(segment),
+        Value::Array(array) => array.get(segment.parse::<usize>().ok()?),
+        _ => None
+    })
+}
+
+/// # Check Set Path
+///
+/// Make sure `set_path` can assign at a path of keys separated by `sep` without replacing anything but nulls,
+/// or fail with `FigConError::PathBlocked` naming the value in the way
+///
+/// Objects (and nulls, which become objects) can take any key, arrays only an in-bounds index, and scalars nothing
+pub(crate) fn check_set_path(root: &Value, path: &str, sep: char) -> Result<(), FigConError> {
+    let mut current = root;
+    let mut walked: usize = 0; // length of the path leading to `current`
+    for segment in path.split(sep) {
+        let child = match current {
+            Value::Object(object) => object.get(segment),
+            Value::Array(items) => match segment.parse::<usize>().ok().and_then(|index| items.get(index)) {
+                Some(child) => Some(child),
+                None => return Err(FigConError::PathBlocked { path: path[..walked.saturating_sub(1)].to_owned() })
+            },
+            Value::Null => None,
+            _ => return Err(FigConError::PathBlocked { path: path[..walked.saturating_sub(1)].to_owned() })
+        };
+        let Some(child) = child else { return Ok(()); }; // everything from here on is created
+        current = child;
+        walked += segment.len() + sep.len_utf8();
+    }
+    Ok(())
+}
+
+/// # Set Path
+///
+/// Assign a value at a path of keys separated by `sep`, creating intermediate objects on the way
+///
+/// Numeric segments index into arrays and must be in bounds. Nulls in the way become objects, but any other value
+/// (a scalar, or an array with an unusable index) fails with `FigConError::PathBlocked`, leaving `root` untouched
+pub(crate) fn set_path(root: &mut Value, path: &str, sep: char, value: Value) -> Result<(), FigConError> {
+    check_set_path(root, path, sep)?;
+    let mut current = root;
+    let mut segments = path.split(sep).peekable();
+    while let Some(segment) = segments.next() {
+        let last = segments.peek().is_none();
+        if let Value::Array(items) = current {
+            let index = segment.parse::<usize>().expect("checked above");
+            if last {
+                items[index] = value;
+                return Ok(());
+            }
+            current = &mut items[index];
+            continue;
+        }
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        let object = current.as_object_mut().expect("checked above");
+        if last {
+            object.insert(segment.to_owned(), value);
+            return Ok(());
+        }
+        current = object.entry(segment.to_owned()).or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+/// # Remove Path
+///
+/// Remove whatever a path of keys separated by `sep` points at, returning it
+///
+/// Numeric segments index into arrays and removing an array element shifts the rest down.
+/// Returns None (leaving the value untouched) if any segment is missing
+pub(crate) fn remove_path(root: &mut Value, path: &str, sep: char) -> Option<Value> {
+    let (parent, last) = match path.rsplit_once(sep) {
+        Some((parent, last)) => (get_path_mut(root, parent, sep)?, last),
+        None => (root, path)
+    };
+    match parent {
+        Value::Object(object) => object.remove(last),
+        Value::Array(items) => {
+            let index = last.parse::<usize>().ok().filter(|index| *index < items.len())?;
+            Some(items.remove(index))
+        },
+        _ => None
+    }
+}
+
+/// # Get Path (Mutable)
+///
+/// Follow a dot-path like `get_path`, but for editing what it points at
+fn get_path_mut<'a>(root: &'a mut Value, path: &str, sep: char) -> Option<&'a mut Value> {
+    path.split(sep).try_fold(root, |current, segment| match current {
+        Value::Object(object) => object.get_mut(segment),
+        Value::Array(array) => array.get_mut(segment.parse::<usize>().ok()?),
+        _ => None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::FigConError;
+    use super::set_path;
+
+    fn blocked_at(result: Result<(), FigConError>) -> String {
+        match result {
+            Err(FigConError::PathBlocked { path }) => path,
+            other => panic!("expected a blocked path, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn creates_objects_and_indexes_arrays() {
+        let mut root = json!({ "servers": [{ "host": "a" }], "cache": null });
+        set_path(&mut root, "servers.0.port", '.', json!(80)).unwrap();
+        set_path(&mut root, "cache.ttl", '.', json!(60)).unwrap();
+        set_path(&mut root, "log.file.path", '.', json!("out.log")).unwrap();
+        assert_eq!(root, json!({
+            "servers": [{ "host": "a", "port": 80 }],
+            "cache": { "ttl": 60 },
+            "log": { "file": { "path": "out.log" } }
+        }));
+    }
+
+    #[test]
+    fn refuses_to_replace_scalars() {
+        let original = json!({ "port": 8080, "servers": ["a"], "tls": { "on": true } });
+        let mut root = original.clone();
+        assert_eq!(blocked_at(set_path(&mut root, "port.number", '.', json!(1))), "port");
+        assert_eq!(blocked_at(set_path(&mut root, "tls.on.really", '.', json!(1))), "tls.on");
+        assert_eq!(blocked_at(set_path(&mut root, "servers.5", '.', json!("b"))), "servers");
+        assert_eq!(blocked_at(set_path(&mut root, "servers.x.y", '.', json!("b"))), "servers");
+        assert_eq!(blocked_at(set_path(&mut json!(7), "a", '.', json!(1))), "");
+        assert_eq!(root, original);
+    }
+}
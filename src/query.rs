@@ -0,0 +1,55 @@
+use serde_json::Value;
+
+/// # Encode Component
+///
+/// Percent-encode a string for `application/x-www-form-urlencoded` (Spaces become `+`)
+pub(crate) fn encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    encoded
+}
+
+/// # Decode Component
+///
+/// Reverse `encode`, leaving malformed escapes as they are
+pub(crate) fn decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len() && let (Some(high), Some(low)) = (hex(bytes[index + 1]), hex(bytes[index + 2])) => {
+                decoded.push(high << 4 | low);
+                index += 2;
+            },
+            byte => decoded.push(byte)
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// # Hex Digit
+///
+/// The value of a single hexadecimal digit
+fn hex(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
+
+/// # Scalar Text
+///
+/// Render a value as the text of a query value (Strings unquoted, null as empty, containers as compact JSON)
+pub(crate) fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        other => other.to_string()
+    }
+}
@@ -0,0 +1,15 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// # Reload Outcome
+///
+/// What `FigCon::reload_or_keep()` did
+pub enum ReloadOutcome {
+    /// The file was read again and replaced the config in memory
+    Reloaded,
+    /// The file (or the storage backend's data) was missing, so the config in memory was kept as it was
+    FileMissing,
+    /// The file held exactly the bytes last loaded from it, so the config in memory was kept as it was
+    Unchanged,
+    /// The last reload was less than the reload interval ago (See `FigCon::with_reload_interval()`), so the file wasn't looked at.
+    /// Whatever it holds by the next call past the interval is picked up then
+    Throttled
+}
@@ -0,0 +1,76 @@
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// # Severity
+///
+/// How serious a `FigConIssue` is, from least to most. Callers decide which severities are fatal
+pub enum Severity {
+    /// Worth knowing, nothing is wrong
+    Info,
+    /// Probably a mistake, but the config still loaded as intended (Unknown keys, skipped env vars, ...)
+    Warning,
+    /// Part of the config could not be used, and a fallback was used instead
+    Error
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// # FigCon Issue
+///
+/// One concern found while loading a config
+pub struct FigConIssue {
+    pub severity: Severity,
+    /// The step that found the issue, like `"override"`, `"env"` or `"validate"`
+    pub stage: &'static str,
+    pub message: String
+}
+
+impl Display for FigConIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} ({}): {}", self.severity, self.stage, self.message)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// # FigCon Report
+///
+/// Every non-fatal issue collected during `FigCon::load_with_report()`, so startup code can log them all at once
+pub struct FigConReport {
+    issues: Vec<FigConIssue>
+}
+
+impl FigConReport {
+    /// # Push
+    ///
+    /// Record an issue
+    pub(crate) fn push(&mut self, severity: Severity, stage: &'static str, message: String) {
+        self.issues.push(FigConIssue { severity, stage, message });
+    }
+
+    /// # Issues
+    ///
+    /// Every issue in the order it was found
+    pub fn issues(&self) -> &[FigConIssue] {
+        &self.issues
+    }
+
+    /// # At Least
+    ///
+    /// The issues at or above a severity, like `report.at_least(Severity::Warning)`
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &FigConIssue> {
+        self.issues.iter().filter(move |issue| issue.severity >= severity)
+    }
+
+    /// # Has Errors
+    ///
+    /// Whether any issue is an `Error`
+    pub fn has_errors(&self) -> bool {
+        self.at_least(Severity::Error).next().is_some()
+    }
+
+    /// # Is Empty
+    ///
+    /// Whether the load found nothing to report
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
@@ -0,0 +1,69 @@
+use serde_json::Value;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// # FigCon Root
+///
+/// The shape of a config's top-level value, from `FigCon::root()`
+///
+/// Key methods only work on `Object` roots and index methods (`push`, `get_index`, ...) only on `Array` roots
+pub enum FigConRoot {
+    Object,
+    Array,
+    /// A string, number, bool or null
+    Scalar
+}
+
+impl FigConRoot {
+    /// # Of
+    ///
+    /// The root shape of a value
+    pub(crate) fn of(value: &Value) -> Self {
+        match value {
+            Value::Object(_) => FigConRoot::Object,
+            Value::Array(_) => FigConRoot::Array,
+            _ => FigConRoot::Scalar
+        }
+    }
+}
+
+impl Display for FigConRoot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FigConRoot::Object => write!(f, "object"),
+            FigConRoot::Array => write!(f, "array"),
+            FigConRoot::Scalar => write!(f, "scalar")
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// # FigCon Root Kind
+///
+/// The exact type of a config's top-level value, from `FigCon::root_kind()`
+///
+/// A finer split of `FigConRoot`, whose `Scalar` covers the last four
+pub enum FigConRootKind {
+    Object,
+    Array,
+    String,
+    Number,
+    Bool,
+    Null
+}
+
+impl FigConRootKind {
+    /// # Of
+    ///
+    /// The root kind of a value
+    pub(crate) fn of(value: &Value) -> Self {
+        match value {
+            Value::Object(_) => FigConRootKind::Object,
+            Value::Array(_) => FigConRootKind::Array,
+            Value::String(_) => FigConRootKind::String,
+            Value::Number(_) => FigConRootKind::Number,
+            Value::Bool(_) => FigConRootKind::Bool,
+            Value::Null => FigConRootKind::Null
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    io::{
+        self,
+        ErrorKind
+    },
+    path::{
+        Path,
+        PathBuf
+    }
+};
+
+/// # Generation
+///
+/// The path of an older generation of a file, numbered before the extension (`config.json` becomes `config.2.json`)
+fn generation(path: &Path, number: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, number, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, number)
+    };
+    path.with_file_name(name)
+}
+
+/// # Rotate
+///
+/// Shift every generation up by one (dropping anything past `keep`) and copy the file itself to generation 1,
+/// ready for the new contents to be renamed over it
+///
+/// The file stays in place throughout, so there's never a moment without it. Generation 1 is a hard link to it where
+/// the filesystem allows, and a copy otherwise. With `keep` 0 nothing is kept and there's nothing to do
+pub(crate) fn rotate(path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 {
+        return Ok(());
+    }
+    ignore_missing(fs::remove_file(generation(path, keep)))?;
+    for number in (1..keep).rev() {
+        ignore_missing(fs::rename(generation(path, number), generation(path, number + 1)))?;
+    }
+    let first = generation(path, 1);
+    if fs::hard_link(path, &first).is_err() {
+        fs::copy(path, &first)?;
+    }
+    Ok(())
+}
+
+/// # Ignore Missing
+///
+/// Treat a file that doesn't exist as nothing to do, since generations are filled in over time
+fn ignore_missing(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        other => other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use super::{generation, rotate};
+
+    #[test]
+    fn names_generations_before_the_extension() {
+        let path = std::path::Path::new("/etc/app/config.json");
+        assert_eq!(generation(path, 2), std::path::Path::new("/etc/app/config.2.json"));
+        assert_eq!(generation(std::path::Path::new("config"), 1), std::path::Path::new("config.1"));
+    }
+
+    #[test]
+    fn shifts_generations_and_keeps_the_file() {
+        let dir = std::env::temp_dir().join(format!("figcon-{}-rotate", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        for contents in ["one", "two", "three"] {
+            fs::write(&path, contents).unwrap();
+            rotate(&path, 2).unwrap();
+            assert_eq!(fs::read_to_string(&path).unwrap(), contents); // still there until the new file is renamed over it
+            let temp = dir.join("config.json.tmp");
+            fs::write(&temp, "new").unwrap();
+            fs::rename(&temp, &path).unwrap();
+        }
+        let first = fs::read_to_string(generation(&path, 1)).unwrap();
+        let second = fs::read_to_string(generation(&path, 2)).unwrap();
+        let third = generation(&path, 3).exists();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!((first.as_str(), second.as_str(), third), ("three", "two", false));
+    }
+}
@@ -0,0 +1,137 @@
+use serde_json::{Number, Value};
+use crate::MAX_DEPTH;
+
+/// # Join
+///
+/// Append a key or index to a dot-path
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() { key.to_owned() } else { format!("{}.{}", prefix, key) }
+}
+
+/// # Same Number
+///
+/// Compare numbers by value rather than representation, so `1` and `1.0` are equal
+fn same_number(left: &Number, right: &Number) -> bool {
+    match (left.as_i64(), right.as_i64(), left.as_u64(), right.as_u64()) {
+        (Some(left), Some(right), _, _) => left == right,
+        (_, _, Some(left), Some(right)) => left == right,
+        _ => left.as_f64() == right.as_f64()
+    }
+}
+
+/// # First Mismatch
+///
+/// Find the dot-path of the first place where `actual` doesn't hold the same data as `expected`
+///
+/// Objects compare key by key regardless of order, arrays element by element, and numbers by value.
+/// `depth` is how many containers deep the values sit, and containers from `MAX_DEPTH` down are compared whole
+pub(crate) fn first_mismatch(prefix: &str, expected: &Value, actual: &Value, depth: usize) -> Option<String> {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) if depth < MAX_DEPTH => {
+            if let Some(key) = actual.keys().find(|key| !expected.contains_key(*key)) {
+                return Some(join(prefix, key));
+            }
+            expected.iter().find_map(|(key, value)| match actual.get(key) {
+                Some(other) => first_mismatch(&join(prefix, key), value, other, depth + 1),
+                None => Some(join(prefix, key))
+            })
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() && depth < MAX_DEPTH => {
+            expected.iter().zip(actual).enumerate().find_map(|(index, (value, other))| {
+                first_mismatch(&join(prefix, &index.to_string()), value, other, depth + 1)
+            })
+        }
+        (Value::Number(expected), Value::Number(actual)) if same_number(expected, actual) => None,
+        (expected, actual) if expected == actual && !expected.is_number() => None,
+        _ => Some(prefix.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Map, Value, json};
+    use super::{first_mismatch, join};
+
+    /// A small deterministic xorshift generator, so failures reproduce
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    /// A random value nesting at most `depth` containers deep
+    fn arbitrary(rng: &mut Rng, depth: usize) -> Value {
+        let kinds = if depth == 0 { 6 } else { 8 };
+        match rng.below(kinds) {
+            0 => Value::Null,
+            1 => Value::Bool(rng.below(2) == 0),
+            2 => json!(rng.next() as i64),
+            3 => json!(rng.next()),
+            4 => json!(rng.below(1000) as f64 / 4.0), // quarters survive text exactly
+            5 => Value::String(format!("s{}", rng.below(100))),
+            6 => Value::Array((0..rng.below(4)).map(|_| arbitrary(rng, depth - 1)).collect()),
+            _ => Value::Object((0..rng.below(4)).map(|index| (format!("k{}", index), arbitrary(rng, depth - 1))).collect::<Map<_, _>>())
+        }
+    }
+
+    /// Replace one randomly chosen value with something the generator never makes, returning its dot-path
+    fn mutate(value: &mut Value, rng: &mut Rng, prefix: &str) -> String {
+        let len = match value {
+            Value::Array(items) => items.len(),
+            Value::Object(object) => object.len(),
+            _ => 0
+        };
+        if len == 0 || rng.below(3) == 0 {
+            *value = Value::String("mutated".to_owned());
+            return prefix.to_owned();
+        }
+        let index = rng.below(len as u64) as usize;
+        match value {
+            Value::Array(items) => mutate(&mut items[index], rng, &join(prefix, &index.to_string())),
+            Value::Object(object) => {
+                let (key, child) = object.iter_mut().nth(index).unwrap();
+                let path = join(prefix, key);
+                mutate(child, rng, &path)
+            }
+            _ => unreachable!()
+        }
+    }
+
+    #[test]
+    fn values_match_themselves_after_a_json_round_trip() {
+        let mut rng = Rng(0x5EED_F16C);
+        for _ in 0..500 {
+            let value = arbitrary(&mut rng, 4);
+            let parsed: Value = serde_json::from_slice(&serde_json::to_vec(&value).unwrap()).unwrap();
+            assert_eq!(first_mismatch("", &value, &value, 0), None);
+            assert_eq!(first_mismatch("", &value, &parsed, 0), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn single_changes_are_found_at_their_path() {
+        let mut rng = Rng(0xF16C_5EED);
+        for _ in 0..500 {
+            let value = arbitrary(&mut rng, 4);
+            let mut changed = value.clone();
+            let path = mutate(&mut changed, &mut rng, "");
+            assert_eq!(first_mismatch("", &value, &changed, 0), Some(path), "{} vs {}", value, changed);
+        }
+    }
+
+    #[test]
+    fn numbers_compare_by_value() {
+        assert_eq!(first_mismatch("", &json!({ "a": [1] }), &json!({ "a": [1.0] }), 0), None);
+        assert_eq!(first_mismatch("", &json!({ "a": [1] }), &json!({ "a": [2] }), 0), Some("a.0".to_owned()));
+        assert_eq!(first_mismatch("", &json!({ "a": 1 }), &json!({ "a": 1, "b": 2 }), 0), Some("b".to_owned()));
+    }
+}
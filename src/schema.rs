@@ -0,0 +1,45 @@
+use serde_json::{Map, Value, json};
+use crate::MAX_DEPTH;
+
+/// # Draft
+///
+/// The JSON Schema dialect inferred schemas declare in `$schema`
+pub(crate) const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// # Infer
+///
+/// Describe the structure of a value as a JSON Schema: its type, and for objects every key (all of them required)
+/// and for arrays the schema of their items
+///
+/// Integers are `integer` and other numbers `number`. Array items that don't all share one schema give an `anyOf`
+/// of the distinct ones, and an empty array says nothing about its items
+///
+/// `depth` is how many containers deep `value` sits, and anything from `MAX_DEPTH` down gets the empty schema `{}`
+pub(crate) fn infer(value: &Value, depth: usize) -> Value {
+    match value {
+        _ if depth >= MAX_DEPTH => json!({}),
+        Value::Object(object) => {
+            let properties: Map<String, Value> = object.iter().map(|(key, child)| (key.clone(), infer(child, depth + 1))).collect();
+            let required: Vec<&String> = object.keys().collect();
+            json!({ "type": "object", "properties": properties, "required": required })
+        },
+        Value::Array(items) => {
+            let mut schemas: Vec<Value> = Vec::new();
+            for schema in items.iter().map(|item| infer(item, depth + 1)) {
+                if !schemas.contains(&schema) {
+                    schemas.push(schema);
+                }
+            }
+            match schemas.len() {
+                0 => json!({ "type": "array" }),
+                1 => json!({ "type": "array", "items": schemas.pop() }),
+                _ => json!({ "type": "array", "items": { "anyOf": schemas } })
+            }
+        },
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Number(number) if number.is_i64() || number.is_u64() => json!({ "type": "integer" }),
+        Value::Number(_) => json!({ "type": "number" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Null => json!({ "type": "null" })
+    }
+}
@@ -0,0 +1,55 @@
+use std::env;
+
+/// # Secret Resolver
+/// 
+/// Turns a secret reference stored in the config (like `"env:MY_TOKEN"`) into the actual secret at read time
+/// 
+/// Register extra resolvers with `FigCon::with_secret_resolver()`. They are tried in order until one returns Some
+pub trait SecretResolver: Send + Sync {
+    /// # Resolve
+    /// 
+    /// Return the secret for a reference, or None if this resolver doesn't handle the reference or can't find the secret
+    fn resolve(&self, reference: &str) -> Option<String>;
+
+    /// # Handles
+    /// 
+    /// Whether a string is a reference this resolver understands (like anything starting with `env:`)
+    /// 
+    /// References that some resolver handles but none can resolve read as missing, rather than as the raw reference string
+    fn handles(&self, reference: &str) -> bool;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// # Env Resolver
+/// 
+/// Resolves `"env:NAME"` to the environment variable `NAME`
+pub struct EnvResolver;
+
+impl SecretResolver for EnvResolver {
+    fn resolve(&self, reference: &str) -> Option<String> {
+        env::var(reference.strip_prefix("env:")?).ok()
+    }
+
+    fn handles(&self, reference: &str) -> bool {
+        reference.starts_with("env:")
+    }
+}
+
+#[cfg(feature = "keyring")]
+#[derive(Clone, Copy, Debug, Default)]
+/// # Keyring Resolver
+/// 
+/// Resolves `"keyring:service/account"` from the platform credential store (Keychain, Credential Manager, Secret Service)
+pub struct KeyringResolver;
+
+#[cfg(feature = "keyring")]
+impl SecretResolver for KeyringResolver {
+    fn resolve(&self, reference: &str) -> Option<String> {
+        let (service, account) = reference.strip_prefix("keyring:")?.split_once('/')?;
+        keyring::Entry::new(service, account).ok()?.get_password().ok()
+    }
+
+    fn handles(&self, reference: &str) -> bool {
+        reference.starts_with("keyring:")
+    }
+}
@@ -0,0 +1,116 @@
+use std::mem;
+use std::sync::{
+    Arc,
+    PoisonError,
+    RwLock
+};
+use serde_json::Value;
+use crate::{FigCon, FigConError};
+
+#[derive(Clone, Debug)]
+/// # Shared FigCon
+///
+/// A thread-safe handle to a FigCon for read-heavy programs, cheap to clone and pass between threads
+///
+/// Reads hand out reference-counted snapshots of the config's value, and writes are copy-on-write:
+/// a mutation only clones the value if some snapshot is still holding the previous version
+pub struct SharedFigCon {
+    current: Arc<RwLock<Shared>>
+}
+
+#[derive(Debug)]
+/// # Shared
+///
+/// The config's settings, and its value kept apart in an `Arc` so snapshots can share it
+struct Shared {
+    /// Everything but the value, whose live config is only filled in for the length of an update
+    conf: FigCon,
+    value: Arc<Value>
+}
+
+impl SharedFigCon {
+    /// # New Shared FigCon
+    ///
+    /// Wrap a config so it can be shared between threads
+    pub fn new(mut conf: FigCon) -> Self {
+        let value = Arc::new(mem::take(&mut conf.live_config));
+        SharedFigCon { current: Arc::new(RwLock::new(Shared { conf, value })) }
+    }
+
+    /// # Snapshot
+    ///
+    /// Get a cheap reference-counted view of the config's value (an `Arc<Value>`, not a whole FigCon) as it is right now,
+    /// which can be held without blocking writers
+    ///
+    /// A snapshot never changes. Holding old snapshots keeps their whole value alive in memory,
+    /// and makes the next write clone the value, so drop them once you are done reading
+    pub fn snapshot(&self) -> Arc<Value> {
+        self.current.read().unwrap_or_else(PoisonError::into_inner).value.clone()
+    }
+
+    /// # Update
+    ///
+    /// Mutate the config in place, cloning its value first only if snapshots of the current version are still alive
+    ///
+    /// If `edit` panics, the value keeps whatever it had been changed to so far, just like a plain FigCon would
+    pub fn update<R>(&self, edit: impl FnOnce(&mut FigCon) -> R) -> R {
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let Shared { conf, value } = &mut *current;
+        conf.live_config = mem::take(Arc::make_mut(value));
+        let restore = Restore { conf, value };
+        edit(restore.conf)
+    }
+
+    /// # Save Config
+    ///
+    /// Write the current config synchronously to the file system
+    ///
+    /// Saving holds the write lock like `.update()` does, so it clones the value too while snapshots are alive
+    pub fn try_save(&self) -> Result<(), FigConError> {
+        self.update(|conf| conf.try_save())
+    }
+}
+
+/// # Restore
+///
+/// Moves the value back out of the config once an update ends, panics included, so it's never left empty
+struct Restore<'a> {
+    conf: &'a mut FigCon,
+    value: &'a mut Arc<Value>
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        *Arc::make_mut(self.value) = mem::take(&mut self.conf.live_config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::{panic, path::PathBuf};
+    use crate::FigCon;
+    use super::SharedFigCon;
+
+    #[test]
+    fn panicking_update_keeps_the_value() {
+        let shared = SharedFigCon::new(FigCon::from_parts(json!({ "port": 8080 }), PathBuf::new()));
+        let panicked = panic::catch_unwind(panic::AssertUnwindSafe(|| shared.update(|conf| {
+            conf.set_key_st("host", json!("localhost"));
+            panic!("edit failed");
+        })));
+        assert!(panicked.is_err());
+        assert_eq!(*shared.snapshot(), json!({ "port": 8080, "host": "localhost" }));
+        shared.update(|conf| conf.set_key_st("port", json!(9090)));
+        assert_eq!(*shared.snapshot(), json!({ "port": 9090, "host": "localhost" }));
+    }
+
+    #[test]
+    fn snapshots_outlive_updates() {
+        let shared = SharedFigCon::new(FigCon::from_parts(json!({ "port": 8080 }), PathBuf::new()));
+        let before = shared.snapshot();
+        shared.update(|conf| conf.set_key_st("port", json!(9090)));
+        assert_eq!(*before, json!({ "port": 8080 }));
+        assert_eq!(*shared.snapshot(), json!({ "port": 9090 }));
+    }
+}
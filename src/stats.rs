@@ -0,0 +1,102 @@
+use serde_json::Value;
+use crate::MAX_DEPTH;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// # FigCon Stats
+/// 
+/// A summary of a config's size and shape, from `FigCon::stats()`
+/// 
+/// Value counts include the root and every nested value, array elements included
+pub struct FigConStats {
+    /// Object keys at every level
+    pub keys: usize,
+    /// How deeply containers nest (A flat object is 1, a scalar root is 0)
+    pub max_depth: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    pub strings: usize,
+    pub numbers: usize,
+    pub bools: usize,
+    pub nulls: usize,
+    /// Size of the config as `.save()` would write it
+    pub bytes: usize
+}
+
+impl FigConStats {
+    /// # Walk
+    /// 
+    /// Count a value and everything below it, found `depth` containers deep
+    /// 
+    /// Containers from `MAX_DEPTH` down are counted but not descended into
+    pub(crate) fn walk(&mut self, value: &Value, depth: usize) {
+        if depth >= MAX_DEPTH && (value.is_array() || value.is_object()) {
+            self.max_depth = self.max_depth.max(depth + 1);
+            if value.is_array() { self.arrays += 1 } else { self.objects += 1 }
+            return;
+        }
+        match value {
+            Value::Null => self.nulls += 1,
+            Value::Bool(_) => self.bools += 1,
+            Value::Number(_) => self.numbers += 1,
+            Value::String(_) => self.strings += 1,
+            Value::Array(items) => {
+                self.arrays += 1;
+                self.max_depth = self.max_depth.max(depth + 1);
+                items.iter().for_each(|item| self.walk(item, depth + 1));
+            },
+            Value::Object(object) => {
+                self.objects += 1;
+                self.keys += object.len();
+                self.max_depth = self.max_depth.max(depth + 1);
+                object.values().for_each(|item| self.walk(item, depth + 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use super::{FigConStats, MAX_DEPTH};
+
+    fn walked(value: &Value) -> FigConStats {
+        let mut stats = FigConStats::default();
+        stats.walk(value, 0);
+        stats
+    }
+
+    #[test]
+    fn counts_nested_fixture() {
+        let value = json!({
+            "name": "x",
+            "port": 1,
+            "tls": { "on": true, "ciphers": ["a", "b"], "cert": null },
+            "weights": [1.5, [2]]
+        });
+        assert_eq!(walked(&value), FigConStats {
+            keys: 7,
+            max_depth: 3,
+            objects: 2,
+            arrays: 3,
+            strings: 3,
+            numbers: 3,
+            bools: 1,
+            nulls: 1,
+            bytes: 0
+        });
+    }
+
+    #[test]
+    fn scalar_root_has_no_depth() {
+        assert_eq!(walked(&json!("x")), FigConStats { strings: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn stops_descending_at_max_depth() {
+        let value = (0..MAX_DEPTH + 5).fold(json!(0), |inner, _| Value::Array(vec![inner]));
+        let stats = walked(&value);
+        assert_eq!(stats.arrays, MAX_DEPTH + 1); // the container at MAX_DEPTH is counted, nothing below it
+        assert_eq!(stats.max_depth, MAX_DEPTH + 1);
+        assert_eq!(stats.numbers, 0);
+    }
+}
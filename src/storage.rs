@@ -0,0 +1,92 @@
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+    sync::{
+        Mutex,
+        PoisonError
+    }
+};
+
+/// # Storage
+/// 
+/// Where a config's serialized bytes live, so a FigCon can persist to anything (a database, object storage, ...)
+/// 
+/// Register one with `FigCon::with_storage()`. Reads that fail with `io::ErrorKind::NotFound` give an empty config
+pub trait Storage: Send + Sync {
+    /// # Read
+    /// 
+    /// Fetch the stored bytes
+    fn read(&self) -> io::Result<Vec<u8>>;
+
+    /// # Write
+    /// 
+    /// Replace the stored bytes
+    fn write(&self, bytes: &[u8]) -> io::Result<()>;
+}
+
+#[derive(Clone, Debug)]
+/// # File Storage
+/// 
+/// Plain file storage, the same thing a FigCon does with its own path
+pub struct FileStorage {
+    pub path: PathBuf
+}
+
+impl FileStorage {
+    pub fn new(path: PathBuf) -> Self {
+        FileStorage { path }
+    }
+}
+
+impl Storage for FileStorage {
+    fn read(&self) -> io::Result<Vec<u8>> {
+        fs::read(&self.path)
+    }
+
+    fn write(&self, bytes: &[u8]) -> io::Result<()> {
+        fs::write(&self.path, bytes)
+    }
+}
+
+#[derive(Debug, Default)]
+/// # Memory Storage
+/// 
+/// Keeps the bytes in memory, which is handy for tests
+pub struct MemoryStorage {
+    bytes: Mutex<Option<Vec<u8>>>
+}
+
+impl MemoryStorage {
+    /// # New Memory Storage
+    /// 
+    /// Start empty, as if the file didn't exist yet
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// # With Contents
+    /// 
+    /// Start with some bytes already stored
+    pub fn with_contents(bytes: Vec<u8>) -> Self {
+        MemoryStorage { bytes: Mutex::new(Some(bytes)) }
+    }
+
+    /// # Contents
+    /// 
+    /// The bytes last written, if anything has been stored
+    pub fn contents(&self) -> Option<Vec<u8>> {
+        self.bytes.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self) -> io::Result<Vec<u8>> {
+        self.contents().ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+
+    fn write(&self, bytes: &[u8]) -> io::Result<()> {
+        *self.bytes.lock().unwrap_or_else(PoisonError::into_inner) = Some(bytes.to_vec());
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use serde_json::Value;
+
+/// # Preview Items
+///
+/// How many array elements a table row shows before truncating to a count
+const PREVIEW_ITEMS: usize = 3;
+
+/// # Render
+///
+/// Lay out (dot-path, value) rows as aligned `key = value (type)` lines, with `<redacted>` for secret rows
+pub(crate) fn render(rows: &[(String, Value)], is_secret: impl Fn(&str) -> bool) -> String {
+    let width = rows.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+    let mut table = String::new();
+    for (key, value) in rows {
+        let shown = if is_secret(key) { "<redacted>".to_owned() } else { preview(value, true) };
+        table.push_str(&format!("{:width$} = {} ({})\n", key, shown, kind(value), width = width));
+    }
+    table
+}
+
+/// # Kind
+///
+/// Name a value's type for the annotation, with the length of non-empty arrays
+fn kind(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(_) => "bool".to_owned(),
+        Value::Number(_) => "number".to_owned(),
+        Value::String(_) => "string".to_owned(),
+        Value::Array(items) if !items.is_empty() => format!("array, {} items", items.len()),
+        Value::Array(_) => "array".to_owned(),
+        Value::Object(_) => "object".to_owned()
+    }
+}
+
+/// # Preview
+///
+/// Write a value compactly, showing only the first few elements of an array
+///
+/// Containers nested inside an array are abbreviated to `[…]` or `{…}`
+fn preview(value: &Value, top: bool) -> String {
+    match value {
+        Value::Array(items) if top => {
+            let mut shown: Vec<String> = items.iter().take(PREVIEW_ITEMS).map(|item| preview(item, false)).collect();
+            if items.len() > PREVIEW_ITEMS {
+                shown.push("…".to_owned());
+            }
+            format!("[{}]", shown.join(", "))
+        },
+        Value::Array(items) if !items.is_empty() => "[…]".to_owned(),
+        Value::Object(object) if !object.is_empty() => "{…}".to_owned(),
+        value => value.to_string()
+    }
+}
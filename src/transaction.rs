@@ -0,0 +1,169 @@
+use serde_json::Value;
+use crate::{FigConError, FigConRoot, ValueExtensions, check_depth, check_replace, path};
+
+/// # FigCon Transaction
+/// 
+/// A working copy of the config handed to the closure of `FigCon::transaction()` or `FigCon::try_transaction()`
+/// 
+/// Edits only reach the real config once the closure returns `Ok`
+pub struct FigConTxn {
+    pub(crate) working: Value,
+    pub(crate) touched: Vec<String>,
+    sep: char,
+    strict_paths: bool
+}
+
+impl FigConTxn {
+    pub(crate) fn new(working: Value, sep: char, strict_paths: bool) -> Self {
+        FigConTxn { working, touched: Vec::new(), sep, strict_paths }
+    }
+
+    /// # Touch
+    /// 
+    /// Remember a key was edited so the commit can journal it
+    fn touch(&mut self, key: &str) {
+        if !self.touched.iter().any(|touched| touched == key) {
+            self.touched.push(key.to_owned());
+        }
+    }
+
+    /// # Top Key
+    /// 
+    /// The top-level key a path starts with
+    fn top_key<'a>(&self, path: &'a str) -> &'a str {
+        path.split(self.sep).next().unwrap_or(path)
+    }
+
+    /// # Get Key
+    /// 
+    /// Acquire a key's value as it stands inside the transaction
+    pub fn get_key(&self, key: String) -> Option<&Value> {
+        self.get_key_st(&key)
+    }
+
+    /// # Get Key (Static)
+    /// 
+    /// Acquire a key's value as it stands inside the transaction
+    pub fn get_key_st(&self, key: &str) -> Option<&Value> {
+        self.working.get_key_st(key)
+    }
+
+    /// # Get Key Path
+    /// 
+    /// Acquire a nested value by dot-path (with the config's path separator) as it stands inside the transaction
+    pub fn get_key_path(&self, path: &str) -> Option<&Value> {
+        path::get_path(&self.working, path, self.sep)
+    }
+
+    /// # Has Key
+    /// 
+    /// Check if a key exists inside the transaction
+    pub fn has_key(&self, key: String) -> bool {
+        self.has_key_st(&key)
+    }
+
+    /// # Has Key (Static)
+    /// 
+    /// Check if a key exists inside the transaction
+    pub fn has_key_st(&self, key: &str) -> bool {
+        self.working.has_key_st(key)
+    }
+
+    /// # Set Key
+    /// 
+    /// Assign a key's value inside the transaction
+    /// 
+    /// Panics right away, before anything is committed, where `try_set_key` would fail
+    pub fn set_key(&mut self, key: String, value: Value) {
+        self.try_set_key(key, value).expect("Refused to set config key");
+    }
+
+    /// # Set Key (Static)
+    /// 
+    /// Assign a key's value inside the transaction
+    pub fn set_key_st(&mut self, key: &str, value: Value) {
+        self.set_key(key.to_owned(), value);
+    }
+
+    /// # Try Set Key
+    /// 
+    /// Assign a key's value inside the transaction, or fail with `FigConError::TooDeep` for a value nesting past `FigCon::MAX_DEPTH`,
+    /// or with `FigConError::WouldReplaceContainer` where the config's strict paths refuse it (See `FigCon::with_strict_paths()`)
+    pub fn try_set_key(&mut self, key: String, value: Value) -> Result<(), FigConError> {
+        check_depth(&value, 1)?;
+        check_replace(self.strict_paths, self.working.get_key_st(&key), &value, &key)?;
+        self.touch(&key);
+        self.working.set_key(key, value);
+        Ok(())
+    }
+
+    /// # Try Set Key (Static)
+    /// 
+    /// Assign a key's value inside the transaction, failing like `try_set_key`
+    pub fn try_set_key_st(&mut self, key: &str, value: Value) -> Result<(), FigConError> {
+        self.try_set_key(key.to_owned(), value)
+    }
+
+    /// # Set Key Path
+    /// 
+    /// Assign a nested value by dot-path inside the transaction, creating intermediate objects as needed like `FigCon::set_key_path()`
+    /// 
+    /// Panics right away, before anything is committed, where `try_set_key_path` would fail
+    pub fn set_key_path(&mut self, path: &str, value: Value) {
+        self.try_set_key_path(path, value).expect("Refused to set config path");
+    }
+
+    /// # Try Set Key Path
+    /// 
+    /// Assign a nested value by dot-path inside the transaction, failing like `FigCon::try_set_key_path()`
+    /// with `FigConError::WouldReplaceContainer`, `FigConError::TooDeep`, `FigConError::PathBlocked` or `FigConError::WrongRoot`
+    pub fn try_set_key_path(&mut self, path: &str, value: Value) -> Result<(), FigConError> {
+        if !self.working.is_object() {
+            return Err(FigConError::WrongRoot { expected: FigConRoot::Object, found: FigConRoot::of(&self.working) });
+        }
+        check_replace(self.strict_paths, path::get_path(&self.working, path, self.sep), &value, path)?;
+        check_depth(&value, path.split(self.sep).count())?;
+        path::set_path(&mut self.working, path, self.sep, value)?;
+        self.touch(self.top_key(path));
+        Ok(())
+    }
+
+    /// # Remove and Get Key
+    /// 
+    /// Remove a key inside the transaction and return its value if it existed
+    pub fn remove_get_key(&mut self, key: String) -> Option<Value> {
+        self.remove_get_key_st(&key)
+    }
+
+    /// # Remove and Get Key (Static)
+    /// 
+    /// Remove a key inside the transaction and return its value if it existed
+    pub fn remove_get_key_st(&mut self, key: &str) -> Option<Value> {
+        let removed = self.working.remove_get_key_st(key)?;
+        self.touch(key);
+        Some(removed)
+    }
+
+    /// # Remove Key
+    /// 
+    /// Remove a key inside the transaction
+    pub fn remove_key(&mut self, key: String) {
+        self.remove_key_st(&key);
+    }
+
+    /// # Remove Key (Static)
+    /// 
+    /// Remove a key inside the transaction
+    pub fn remove_key_st(&mut self, key: &str) {
+        self.remove_get_key_st(key);
+    }
+
+    /// # Remove Key Path
+    /// 
+    /// Remove a nested value by dot-path inside the transaction and return it if it existed
+    pub fn remove_key_path(&mut self, path: &str) -> Option<Value> {
+        let removed = path::remove_path(&mut self.working, path, self.sep)?;
+        self.touch(self.top_key(path));
+        Some(removed)
+    }
+}
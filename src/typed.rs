@@ -0,0 +1,120 @@
+use serde::de::{
+    DeserializeOwned,
+    DeserializeSeed,
+    Deserializer,
+    Error,
+    MapAccess,
+    SeqAccess,
+    Visitor,
+    value::BorrowedStrDeserializer
+};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+use std::cell::RefCell;
+
+/// # Deserialize Traced
+///
+/// Deserialize a value into `T`, and on failure also report the dot-path of the value that was rejected
+///
+/// The plain deserialization runs first. Only when it fails is the value walked again with a tracking deserializer,
+/// which notes every key and index on the way down and stops where the error happens. Errors found only after a whole
+/// object was read (like a missing field) point at that object
+pub(crate) fn deserialize_traced<T: DeserializeOwned>(value: &Value) -> Result<T, (String, serde_json::Error)> {
+    let error = match T::deserialize(value) {
+        Ok(typed) => return Ok(typed),
+        Err(error) => error
+    };
+    let path = RefCell::new(Vec::new());
+    let _ = T::deserialize(Tracked { value, path: &path });
+    Err((path.into_inner().join("."), error))
+}
+
+/// # Tracked
+///
+/// A deserializer over a borrowed value that pushes each key and index it descends into onto `path`,
+/// popping them again only when that part deserialized fine
+struct Tracked<'a, 'p> {
+    value: &'a Value,
+    path: &'p RefCell<Vec<String>>
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(object) => visitor.visit_map(TrackedMap { iter: object.iter(), pending: None, path: self.path }),
+            Value::Array(items) => visitor.visit_seq(TrackedSeq { iter: items.iter().enumerate(), path: self.path }),
+            other => other.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor) // enums aren't descended into
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct TrackedMap<'a, 'p> {
+    iter: serde_json::map::Iter<'a>,
+    pending: Option<(&'a String, &'a Value)>,
+    path: &'p RefCell<Vec<String>>
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.iter.next() else { return Ok(None); };
+        self.pending = Some((key, value));
+        self.path.borrow_mut().push(key.clone());
+        let key = seed.deserialize(BorrowedStrDeserializer::new(key))?;
+        self.path.borrow_mut().pop();
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        let (key, value) = self.pending.take().ok_or_else(|| serde_json::Error::custom("value requested before its key"))?;
+        self.path.borrow_mut().push(key.clone());
+        let value = seed.deserialize(Tracked { value, path: self.path })?;
+        self.path.borrow_mut().pop();
+        Ok(value)
+    }
+}
+
+struct TrackedSeq<'a, 'p> {
+    iter: std::iter::Enumerate<std::slice::Iter<'a, Value>>,
+    path: &'p RefCell<Vec<String>>
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'de, '_> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        let Some((index, value)) = self.iter.next() else { return Ok(None); };
+        self.path.borrow_mut().push(index.to_string());
+        let value = seed.deserialize(Tracked { value, path: self.path })?;
+        self.path.borrow_mut().pop();
+        Ok(Some(value))
+    }
+}
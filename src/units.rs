@@ -0,0 +1,102 @@
+use core::time::Duration;
+
+/// # Split Quantity
+///
+/// Separate a string like "10MB" or "1.5 s" into its numeric part and its (trimmed) unit suffix
+fn split_quantity(input: &str) -> Option<(f64, &str)> {
+    let input = input.trim();
+    let split = input.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    if number.is_empty() { return None; }
+    let number: f64 = number.parse().ok()?;
+    Some((number, unit.trim()))
+}
+
+/// # Parse Bytes
+///
+/// Parse a human readable byte size into a number of bytes
+///
+/// Units are case-insensitive. `KB`, `MB`, `GB` and `TB` are decimal (powers of 1000), `KiB`, `MiB`, `GiB` and `TiB`
+/// are binary (powers of 1024), and the bare letters `K`, `M`, `G` and `T` are binary too, as in most config formats:
+///
+/// `B`, `KB`/`KiB`/`K`, `MB`/`MiB`/`M`, `GB`/`GiB`/`G`, `TB`/`TiB`/`T`
+///
+/// A bare number is treated as bytes. Returns None for unknown units, negative values or overflow
+pub(crate) fn parse_bytes(input: &str) -> Option<u64> {
+    let (number, unit) = split_quantity(input)?;
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        _ => return None
+    };
+    let bytes = number * multiplier as f64;
+    if !bytes.is_finite() || bytes > u64::MAX as f64 { return None; }
+    Some(bytes as u64)
+}
+
+/// # Parse Duration
+///
+/// Parse a human readable duration into a Duration
+///
+/// Units are case-insensitive:
+///
+/// `ns`, `us`/`µs`, `ms`, `s`/`sec`, `m`/`min`, `h`/`hr`, `d`/`day`
+///
+/// A bare number is treated as seconds. Returns None for unknown units, negative values or overflow
+pub(crate) fn parse_duration(input: &str) -> Option<Duration> {
+    let (number, unit) = split_quantity(input)?;
+    let seconds: f64 = match unit.to_lowercase().as_str() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "" | "s" | "sec" | "secs" => 1.0,
+        "m" | "min" | "mins" => 60.0,
+        "h" | "hr" | "hrs" => 3600.0,
+        "d" | "day" | "days" => 86400.0,
+        _ => return None
+    };
+    Duration::try_from_secs_f64(number * seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use super::{parse_bytes, parse_duration};
+
+    #[test]
+    fn decimal_and_binary_byte_units_differ() {
+        assert_eq!(parse_bytes("1KB"), Some(1_000));
+        assert_eq!(parse_bytes("1KiB"), Some(1_024));
+        assert_ne!(parse_bytes("1KB"), parse_bytes("1KiB"));
+        assert_eq!(parse_bytes("10 MB"), Some(10_000_000));
+        assert_eq!(parse_bytes("10 mib"), Some(10 * 1024 * 1024));
+        assert_eq!(parse_bytes("2GB"), Some(2_000_000_000));
+        assert_eq!(parse_bytes("2GiB"), Some(2 << 30));
+        assert_eq!(parse_bytes("1TB"), Some(1_000_000_000_000));
+        assert_eq!(parse_bytes("1.5K"), Some(1_536));
+        assert_eq!(parse_bytes("512"), Some(512));
+    }
+
+    #[test]
+    fn rejects_bad_byte_sizes() {
+        assert_eq!(parse_bytes("1XB"), None);
+        assert_eq!(parse_bytes("-1KB"), None);
+        assert_eq!(parse_bytes("KB"), None);
+        assert_eq!(parse_bytes("99999999999TiB"), None);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("1.5 s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("30"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1 fortnight"), None);
+    }
+}
@@ -0,0 +1,50 @@
+use std::io;
+use wasm_bindgen::JsCast;
+use web_sys::DomException;
+use crate::Storage;
+
+#[derive(Clone, Debug)]
+/// # Web Storage
+/// 
+/// Stores the config in the browser's `window.localStorage` under a key, for Rust apps compiled to wasm
+/// 
+/// localStorage only holds strings, so use it with `Format::Json`. Paths are ignored in this mode.
+/// A full quota makes saving fail with `FigConError::QuotaExceeded`
+pub struct WebStorage {
+    pub key: String
+}
+
+impl WebStorage {
+    pub fn new(key: &str) -> Self {
+        WebStorage { key: key.to_owned() }
+    }
+
+    /// # Local Storage
+    /// 
+    /// The window's localStorage, if this is running in a browser that allows it
+    fn local_storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "localStorage is unavailable"))
+    }
+}
+
+impl Storage for WebStorage {
+    fn read(&self) -> io::Result<Vec<u8>> {
+        let stored = Self::local_storage()?
+            .get_item(&self.key)
+            .map_err(|_| io::Error::other("localStorage read was denied"))?;
+        stored.map(String::into_bytes).ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+
+    fn write(&self, bytes: &[u8]) -> io::Result<()> {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "localStorage can only hold text formats"))?;
+        Self::local_storage()?.set_item(&self.key, text).map_err(|err| {
+            match err.dyn_ref::<DomException>() {
+                Some(exception) if exception.name() == "QuotaExceededError" => io::ErrorKind::StorageFull.into(),
+                _ => io::Error::other("localStorage write was denied")
+            }
+        })
+    }
+}